@@ -0,0 +1,42 @@
+use serde::Deserialize;
+
+/// A published security advisory of a repository.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_security_advisories()`](crate::Client::get_security_advisories)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhAdvisory {
+    /// GitHub Security Advisory ID, e.g. `"GHSA-xxxx-xxxx-xxxx"`.
+    pub ghsa_id: String,
+    pub severity: Option<GhAdvisorySeverity>,
+    pub summary: String,
+    /// ISO 8601 timestamp, e.g. `"2023-03-23T02:30:56Z"`.
+    ///
+    /// `None` if the advisory has not been published.
+    pub published_at: Option<String>,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(from = "String")]
+pub enum GhAdvisorySeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+    /// Any other severity.
+    Other(String),
+}
+
+impl From<String> for GhAdvisorySeverity {
+    fn from(severity: String) -> Self {
+        match severity.as_str() {
+            "low" => Self::Low,
+            "medium" => Self::Medium,
+            "high" => Self::High,
+            "critical" => Self::Critical,
+            _ => Self::Other(severity),
+        }
+    }
+}
@@ -0,0 +1,604 @@
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_LENGTH, ETAG, IF_NONE_MATCH,
+};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+
+use super::client::user_agent_header;
+#[cfg(feature = "graphql")]
+use super::graphql::{graphql_url, GraphQlRequest};
+use super::metrics::{MetricsHook, RequestMetrics};
+use super::response_cache::{CacheHandle, CachedEntry};
+use super::{
+    api_url, encode, next_page_url, starred_url, CachedRepo, CancellationToken, ClientBuilder,
+    ForkSort, GhRepoInfo, GhRepoInfoError, GhRepoOwnerInfo, GhRepoOwnerKind, LatestRunOptions,
+    ListMilestonesOptions, ListMyReposOptions, ListOrgMembersOptions, Pagination, RateLimiter,
+    RepoFilter,
+};
+
+/// Everything about the GitHub REST API shared by [`Client`](crate::Client)
+/// and the blocking `Client`, i.e. building the request of each endpoint,
+/// and interpreting the responses. The clients only perform the I/O.
+#[derive(Clone, Debug)]
+pub(crate) struct Api {
+    token: Option<String>,
+    pub(crate) user_agent: Option<HeaderValue>,
+    base_url: String,
+    api_version: Option<String>,
+    accept: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+    on_request: Option<MetricsHook>,
+    rate_limiter: Option<RateLimiter>,
+    pub(crate) cache: Option<CacheHandle>,
+    pub(crate) pagination: Pagination,
+    pub(crate) cancellation: Option<CancellationToken>,
+    pub(crate) stream_listings: bool,
+}
+
+/// A request built by [`Api`], which a client turns into a
+/// `reqwest` request.
+pub(crate) struct ApiRequest {
+    pub(crate) method: Method,
+    pub(crate) url: String,
+    pub(crate) query: Vec<(&'static str, String)>,
+    pub(crate) headers: Vec<(HeaderName, String)>,
+    pub(crate) bearer: Option<String>,
+    #[cfg(feature = "graphql")]
+    pub(crate) graphql: Option<GraphQlRequest>,
+    requires_token: bool,
+}
+
+impl Api {
+    pub(crate) fn new(builder: ClientBuilder) -> Self {
+        Self {
+            token: builder.token,
+            user_agent: builder.user_agent.as_deref().and_then(user_agent_header),
+            base_url: builder.base_url,
+            api_version: builder.api_version,
+            accept: builder.accept,
+            timeout: None,
+            on_request: builder.on_request,
+            rate_limiter: builder.rate_limiter,
+            cache: builder.cache,
+            pagination: Pagination::default(),
+            cancellation: None,
+            stream_listings: builder.stream_listings,
+        }
+    }
+
+    pub(crate) fn repo(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        self.get(api_url(&self.base_url, owner, repo))
+    }
+
+    /// Same as [`Api::repo()`], but revalidating `cached` using its `ETag`.
+    pub(crate) fn refresh(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        cached: &Option<CachedRepo>,
+    ) -> ApiRequest {
+        let req = self.repo(owner, repo);
+        match cached.as_ref().and_then(|cached| cached.etag.as_deref()) {
+            Some(etag) => req.header(IF_NONE_MATCH, etag),
+            None => req,
+        }
+    }
+
+    pub(crate) fn branch(&self, owner: &str, repo: &str, branch: &str) -> ApiRequest {
+        self.get(format!(
+            "{}/branches/{}",
+            api_url(&self.base_url, owner, repo),
+            encode(branch),
+        ))
+    }
+
+    pub(crate) fn refs(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        ref_prefix: impl AsRef<str>,
+    ) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/git/matching-refs/{}",
+            api_url(&self.base_url, owner, repo),
+            ref_prefix.as_ref().trim_start_matches("refs/"),
+        ))
+    }
+
+    pub(crate) fn languages(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        self.get(format!(
+            "{}/languages",
+            api_url(&self.base_url, owner, repo)
+        ))
+    }
+
+    pub(crate) fn pages(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        self.get(format!("{}/pages", api_url(&self.base_url, owner, repo)))
+    }
+
+    /// `traffic` is the path below `/traffic`, e.g. `"views"`.
+    pub(crate) fn traffic(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        traffic: &str,
+    ) -> ApiRequest {
+        let url = format!("{}/traffic/{traffic}", api_url(&self.base_url, owner, repo));
+        self.get(url).requires_token()
+    }
+
+    pub(crate) fn my_repos(&self, options: &ListMyReposOptions) -> ApiRequest {
+        self.get_paginated(format!("{}/user/repos", self.base_url))
+            .query(options.query())
+            .requires_token()
+    }
+
+    pub(crate) fn user_repos(&self, user: impl AsRef<str>) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/users/{}/repos",
+            self.base_url,
+            encode(user.as_ref())
+        ))
+    }
+
+    pub(crate) fn org_repos(&self, org: impl AsRef<str>) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/orgs/{}/repos",
+            self.base_url,
+            encode(org.as_ref())
+        ))
+    }
+
+    pub(crate) fn search_repos(&self, query: impl AsRef<str>) -> ApiRequest {
+        self.get(format!("{}/search/repositories", self.base_url))
+            .query(vec![("q", query.as_ref().to_owned())])
+            .query(self.pagination.query())
+    }
+
+    pub(crate) fn org_members(
+        &self,
+        org: impl AsRef<str>,
+        options: &ListOrgMembersOptions,
+    ) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/orgs/{}/members",
+            self.base_url,
+            encode(org.as_ref())
+        ))
+        .query(options.query())
+    }
+
+    pub(crate) fn labels(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        self.get_paginated(format!("{}/labels", api_url(&self.base_url, owner, repo)))
+    }
+
+    pub(crate) fn stargazers(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        let url = format!("{}/stargazers", api_url(&self.base_url, owner, repo));
+        let req = self.request_accept(Method::GET, url, Some("application/vnd.github.star+json"));
+        req.query(self.pagination.query())
+    }
+
+    pub(crate) fn subscribers(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/subscribers",
+            api_url(&self.base_url, owner, repo)
+        ))
+    }
+
+    pub(crate) fn security_advisories(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/security-advisories",
+            api_url(&self.base_url, owner, repo)
+        ))
+        .query(vec![("state", "published".to_owned())])
+    }
+
+    pub(crate) fn forks(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        sort: ForkSort,
+    ) -> ApiRequest {
+        self.get_paginated(format!("{}/forks", api_url(&self.base_url, owner, repo)))
+            .query(vec![("sort", sort.as_str().to_owned())])
+    }
+
+    pub(crate) fn milestones(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        options: &ListMilestonesOptions,
+    ) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/milestones",
+            api_url(&self.base_url, owner, repo)
+        ))
+        .query(options.query())
+    }
+
+    pub(crate) fn environments(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        self.get_paginated(format!(
+            "{}/environments",
+            api_url(&self.base_url, owner, repo)
+        ))
+        .requires_token()
+    }
+
+    /// Workflows are paginated using `page` and `total_count`
+    /// instead of the `Link` header, see [`Api::next_workflows_page()`].
+    pub(crate) fn workflows(&self, owner: &str, repo: &str, page: u32) -> ApiRequest {
+        let url = format!("{}/actions/workflows", api_url(&self.base_url, owner, repo));
+        self.get(url).query(vec![
+            ("per_page", self.pagination.per_page().to_string()),
+            ("page", page.to_string()),
+        ])
+    }
+
+    /// Returns the first page of [`Api::workflows()`].
+    pub(crate) fn first_workflows_page(&self) -> u32 {
+        self.pagination.get_page().unwrap_or(1)
+    }
+
+    /// Returns the page after `page` of [`Api::workflows()`], or `None`
+    /// if `page` was empty, or all `total_count` workflows are fetched.
+    pub(crate) fn next_workflows_page(
+        &self,
+        page: u32,
+        page_len: usize,
+        fetched: usize,
+        total_count: usize,
+    ) -> Option<u32> {
+        if (page_len == 0) || self.pagination.get_page().is_some() {
+            return None;
+        }
+        (fetched < total_count).then_some(page + 1)
+    }
+
+    pub(crate) fn latest_run(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        options: &LatestRunOptions,
+    ) -> ApiRequest {
+        let api_url = api_url(&self.base_url, owner, repo);
+        let url = match options.workflow_id {
+            Some(workflow_id) => format!("{api_url}/actions/workflows/{workflow_id}/runs"),
+            None => format!("{api_url}/actions/runs"),
+        };
+        self.get(url).query(options.query())
+    }
+
+    pub(crate) fn events(&self, owner: impl AsRef<str>, repo: impl AsRef<str>) -> ApiRequest {
+        self.get_paginated(format!("{}/events", api_url(&self.base_url, owner, repo)))
+    }
+
+    #[cfg(feature = "graphql")]
+    pub(crate) fn stars_bulk(&self, repos: &[(String, String)]) -> ApiRequest {
+        let mut req = self
+            .request(Method::POST, graphql_url(&self.base_url))
+            .requires_token();
+        req.graphql = Some(GraphQlRequest::stars_bulk(repos));
+        req
+    }
+
+    /// `method` is `GET` to check, `PUT` to star, and `DELETE` to unstar.
+    pub(crate) fn starred(
+        &self,
+        method: Method,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> ApiRequest {
+        let req = self.request(method.clone(), starred_url(&self.base_url, owner, repo));
+        let req = match method {
+            Method::PUT => req.header(CONTENT_LENGTH, "0"),
+            _ => req,
+        };
+        req.requires_token()
+    }
+
+    pub(crate) fn owner_profile(&self, owner: &GhRepoOwnerInfo) -> ApiRequest {
+        let kind = match owner.kind {
+            GhRepoOwnerKind::Organization => "orgs",
+            GhRepoOwnerKind::User | GhRepoOwnerKind::Bot => "users",
+        };
+        self.get(format!("{}/{kind}/{}", self.base_url, encode(&owner.name)))
+    }
+
+    /// The authenticated user.
+    pub(crate) fn user(&self) -> ApiRequest {
+        self.get(format!("{}/user", self.base_url)).requires_token()
+    }
+
+    /// Authenticated using `jwt` instead of the token.
+    pub(crate) fn installation_token(&self, jwt: &str, installation_id: u64) -> ApiRequest {
+        let url = format!(
+            "{}/app/installations/{installation_id}/access_tokens",
+            self.base_url
+        );
+        let mut req = self
+            .request_without_token(Method::POST, url, self.accept.as_deref())
+            .header(CONTENT_LENGTH, "0");
+        req.bearer = Some(jwt.to_owned());
+        req
+    }
+
+    /// Avatars are served from a different host, so neither
+    /// the token nor any of the API headers are sent.
+    pub(crate) fn avatar(&self, owner: &GhRepoOwnerInfo, size: Option<u32>) -> ApiRequest {
+        let url = match size {
+            Some(size) => owner.avatar_url_sized(size),
+            None => owner.avatar_url.clone(),
+        };
+        ApiRequest::new(Method::GET, url)
+    }
+
+    /// Returns the request for the next page of the listing requested
+    /// by `req`, by following the `Link` header of its response.
+    ///
+    /// Returns `None` if a specific page is requested,
+    /// see [`Pagination::page()`].
+    pub(crate) fn next_page(&self, req: &ApiRequest, headers: &HeaderMap) -> Option<ApiRequest> {
+        if self.pagination.get_page().is_some() {
+            return None;
+        }
+        let url = next_page_url(headers)?;
+        Some(ApiRequest {
+            method: Method::GET,
+            url,
+            query: Vec::new(),
+            headers: req.headers.clone(),
+            bearer: req.bearer.clone(),
+            #[cfg(feature = "graphql")]
+            graphql: None,
+            requires_token: req.requires_token,
+        })
+    }
+
+    /// Fails if `req` cannot be sent, i.e. if it requires a token
+    /// and there is none, or if the `Client` was cancelled.
+    pub(crate) fn check(&self, req: &ApiRequest) -> Result<(), GhRepoInfoError> {
+        if req.requires_token && self.token.is_none() {
+            return Err(GhRepoInfoError::MissingToken);
+        }
+        self.check_cancelled()
+    }
+
+    pub(crate) fn check_cancelled(&self) -> Result<(), GhRepoInfoError> {
+        match &self.cancellation {
+            Some(cancellation) if cancellation.is_cancelled() => Err(GhRepoInfoError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns how long to wait before sending a request,
+    /// if a [`RateLimiter`] is used.
+    pub(crate) fn rate_limit(&self) -> Option<Duration> {
+        let wait = self.rate_limiter.as_ref()?.reserve();
+        (!wait.is_zero()).then_some(wait)
+    }
+
+    /// Reports a request sent at `start` to [`ClientBuilder::on_request()`].
+    pub(crate) fn on_request(
+        &self,
+        url: String,
+        status: Option<StatusCode>,
+        headers: Option<&HeaderMap>,
+        start: Instant,
+    ) {
+        if let Some(on_request) = &self.on_request {
+            on_request.call(&RequestMetrics::new(url, status, headers, start.elapsed()));
+        }
+    }
+
+    /// Adds `If-None-Match` to `req` if `cache` holds a response for it.
+    pub(crate) fn revalidate(
+        &self,
+        cache: &CacheHandle,
+        req: ApiRequest,
+    ) -> (ApiRequest, Option<CachedEntry>) {
+        match cache.get(&req.url) {
+            Some(cached) => (req.header(IF_NONE_MATCH, &cached.etag), Some(cached)),
+            None => (req, None),
+        }
+    }
+
+    fn get(&self, url: String) -> ApiRequest {
+        self.request(Method::GET, url)
+    }
+
+    /// Same as [`Api::get()`], but for listings using [`Api::pagination`].
+    fn get_paginated(&self, url: String) -> ApiRequest {
+        self.get(url).query(self.pagination.query())
+    }
+
+    fn request(&self, method: Method, url: String) -> ApiRequest {
+        self.request_accept(method, url, self.accept.as_deref())
+    }
+
+    /// Same as [`Api::request()`], but overriding the `Accept` header.
+    fn request_accept(&self, method: Method, url: String, accept: Option<&str>) -> ApiRequest {
+        let mut req = self.request_without_token(method, url, accept);
+        req.bearer = self.token.clone();
+        req
+    }
+
+    /// Same as [`Api::request_accept()`], but without the token.
+    fn request_without_token(
+        &self,
+        method: Method,
+        url: String,
+        accept: Option<&str>,
+    ) -> ApiRequest {
+        let mut req = ApiRequest::new(method, url);
+        if let Some(api_version) = &self.api_version {
+            req = req.header(HeaderName::from_static("x-github-api-version"), api_version);
+        }
+        if let Some(accept) = accept {
+            req = req.header(ACCEPT, accept);
+        }
+        req
+    }
+}
+
+impl ApiRequest {
+    fn new(method: Method, url: String) -> Self {
+        Self {
+            method,
+            url,
+            query: Vec::new(),
+            headers: Vec::new(),
+            bearer: None,
+            #[cfg(feature = "graphql")]
+            graphql: None,
+            requires_token: false,
+        }
+    }
+
+    fn query(mut self, query: Vec<(&'static str, String)>) -> Self {
+        self.query.extend(query);
+        self
+    }
+
+    fn header(mut self, name: HeaderName, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    fn requires_token(mut self) -> Self {
+        self.requires_token = true;
+        self
+    }
+}
+
+/// Returns `Ok(None)` if the response was `404 Not Found`.
+pub(crate) fn not_found_as_none<T>(
+    res: Result<T, GhRepoInfoError>,
+) -> Result<Option<T>, GhRepoInfoError> {
+    match res {
+        Ok(value) => Ok(Some(value)),
+        Err(GhRepoInfoError::ResponseNonSuccess(StatusCode::NOT_FOUND, _)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns `Ok(None)` if the response was `304 Not Modified`.
+pub(crate) fn not_modified_as_none<T>(
+    res: Result<T, GhRepoInfoError>,
+) -> Result<Option<T>, GhRepoInfoError> {
+    match res {
+        Ok(value) => Ok(Some(value)),
+        Err(GhRepoInfoError::ResponseNonSuccess(StatusCode::NOT_MODIFIED, _)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns [`GhRepoInfoError::EmptyRepository`] if the response
+/// was `409 Conflict`, which GitHub responds with for git data
+/// of repositories without commits.
+pub(crate) fn conflict_as_empty<T>(res: Result<T, GhRepoInfoError>) -> Result<T, GhRepoInfoError> {
+    match res {
+        Err(GhRepoInfoError::ResponseNonSuccess(StatusCode::CONFLICT, request_id)) => {
+            Err(GhRepoInfoError::EmptyRepository(request_id))
+        }
+        res => res,
+    }
+}
+
+/// Breaks with the value of `cached` if the response to revalidating
+/// it was `304 Not Modified`, otherwise continues with the response.
+pub(crate) fn revalidated<R, T>(
+    res: Result<R, GhRepoInfoError>,
+    cached: Option<CachedEntry>,
+) -> Result<ControlFlow<T, R>, GhRepoInfoError>
+where
+    T: DeserializeOwned,
+{
+    match (res, cached) {
+        (Ok(resp), _cached) => Ok(ControlFlow::Continue(resp)),
+        (
+            Err(GhRepoInfoError::ResponseNonSuccess(StatusCode::NOT_MODIFIED, request_id)),
+            Some(cached),
+        ) => serde_json::from_slice(&cached.body)
+            .map(ControlFlow::Break)
+            .map_err(|err| GhRepoInfoError::DeserializeJsonFailed(err, request_id)),
+        (Err(err), _cached) => Err(err),
+    }
+}
+
+/// Deserializes the `body` of a response, and stores it in `cache`
+/// under `url` if the response has an `ETag`.
+pub(crate) fn store<T>(
+    cache: &CacheHandle,
+    url: &str,
+    etag: Option<String>,
+    request_id: Option<String>,
+    body: &[u8],
+) -> Result<T, GhRepoInfoError>
+where
+    T: DeserializeOwned,
+{
+    let value = serde_json::from_slice(body)
+        .map_err(|err| GhRepoInfoError::DeserializeJsonFailed(err, request_id))?;
+    if let Some(etag) = etag {
+        let body = body.to_vec();
+        cache.put(url, CachedEntry { etag, body });
+    }
+    Ok(value)
+}
+
+/// Collects `full_names`, keeping only the first of any duplicates.
+pub(crate) fn dedup_full_names<I>(full_names: I) -> Vec<String>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut seen = HashSet::new();
+    full_names
+        .into_iter()
+        .map(|full_name| full_name.as_ref().to_owned())
+        .filter(|full_name| seen.insert(full_name.clone()))
+        .collect()
+}
+
+/// Returns the `ETag` header, if any.
+pub(crate) fn etag(headers: &HeaderMap) -> Option<String> {
+    let etag = headers.get(ETAG)?.to_str().ok()?;
+    Some(etag.to_owned())
+}
+
+/// Returns the delay before retrying the failed `attempt`, or `None`
+/// if `should_retry` returns `false`. Cancelled requests are never
+/// retried.
+pub(crate) fn retry_after<F>(
+    err: &GhRepoInfoError,
+    attempt: u32,
+    should_retry: F,
+) -> Option<Duration>
+where
+    F: Fn(&GhRepoInfoError, u32) -> bool,
+{
+    if matches!(err, GhRepoInfoError::Cancelled) || !should_retry(err, attempt) {
+        return None;
+    }
+    Some(super::retry_delay(attempt))
+}
+
+/// Keeps the repositories whose primary language is `language`,
+/// compared case-insensitively, and which are included by `filter`.
+pub(crate) fn retain_language(repos: &mut Vec<GhRepoInfo>, language: &str, filter: &RepoFilter) {
+    repos.retain(|repo| {
+        filter.includes(repo)
+            && repo
+                .language
+                .as_deref()
+                .is_some_and(|lang| lang.eq_ignore_ascii_case(language))
+    });
+}
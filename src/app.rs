@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+/// An access token of a GitHub App installation.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::create_installation_token()`](crate::Client::create_installation_token)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhInstallationToken {
+    /// The installation token.
+    #[cfg_attr(
+        feature = "client",
+        doc = "",
+        doc = "Pass to [`ClientBuilder::token()`](crate::ClientBuilder::token)."
+    )]
+    pub token: String,
+    /// ISO 8601 timestamp, e.g. `"2016-07-11T22:14:10Z"`,
+    /// usually an hour after the token was created.
+    pub expires_at: String,
+}
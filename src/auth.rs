@@ -0,0 +1,16 @@
+/// The user a token authenticates as.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::verify_token()`](crate::Client::verify_token)."
+)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GhAuthInfo {
+    pub login: String,
+    /// OAuth scopes of the token, e.g. `["repo", "read:org"]`, from
+    /// the `X-OAuth-Scopes` header.
+    ///
+    /// Empty for tokens without scopes, e.g. fine-grained personal
+    /// access tokens and GitHub App installation tokens.
+    pub scopes: Vec<String>,
+}
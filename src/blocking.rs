@@ -0,0 +1,1304 @@
+//! The functionality in `gh_repo_info::blocking` must not be executed
+//! within an async runtime, or it will panic when attempting to block.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{self, BufReader, Read};
+use std::ops::ControlFlow;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::vec;
+
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
+use serde::de::{self, DeserializeOwned, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer as _};
+
+use super::api::{
+    conflict_as_empty, dedup_full_names, etag, not_found_as_none, not_modified_as_none,
+    retain_language, retry_after, revalidated, store, Api, ApiRequest,
+};
+use super::environment::GhEnvironments;
+use super::workflow::{GhWorkflowRuns, GhWorkflows};
+use super::{
+    check_content_type, check_status, default_should_retry, oauth_scopes, parse_full_name,
+    request_id, unauthorized, CachedRepo, CancellationToken, ClientBuilder, ForkSort, GhAdvisory,
+    GhAuthInfo, GhBranch, GhEnvironment, GhEvent, GhInstallationToken, GhLabel, GhMilestone,
+    GhOwnerProfile, GhPagesInfo, GhRef, GhRepoInfo, GhRepoInfoError, GhRepoOwnerInfo, GhStargazer,
+    GhTrafficClones, GhTrafficPath, GhTrafficReferrer, GhTrafficViews, GhUserRef, GhWorkflow,
+    GhWorkflowRun, IntoRepoRef, LatestRunOptions, ListMilestonesOptions, ListMyReposOptions,
+    ListOrgMembersOptions, Pagination, PreparedRequest, RepoFilter, RequestOptions,
+    SearchByTopicOptions, SearchResults,
+};
+
+/// Get GitHub repository information given an `owner` and `repo`.
+///
+/// This is a shorthand for [`get_with()`] using [`RequestOptions::default()`].
+pub fn get(owner: impl AsRef<str>, repo: impl AsRef<str>) -> Result<GhRepoInfo, GhRepoInfoError> {
+    get_with(owner, repo, &RequestOptions::default())
+}
+
+/// Same as [`get()`], but taking the `owner` and `repo` as a
+/// single [`IntoRepoRef`], e.g. a tuple.
+pub fn get_repo(repo: impl IntoRepoRef) -> Result<GhRepoInfo, GhRepoInfoError> {
+    get(repo.owner(), repo.repo())
+}
+
+/// Same as [`get()`], but using the given `options`,
+/// see [`crate::get_with()`].
+pub fn get_with(
+    owner: impl AsRef<str>,
+    repo: impl AsRef<str>,
+    options: &RequestOptions,
+) -> Result<GhRepoInfo, GhRepoInfoError> {
+    ClientBuilder::from(options)
+        .build_blocking()?
+        .get(owner, repo)
+}
+
+/// Same as [`get()`], but authenticated using the token stored by the
+/// `gh` CLI, see [`crate::get_with_gh_auth()`].
+pub fn get_with_gh_auth(
+    owner: impl AsRef<str>,
+    repo: impl AsRef<str>,
+) -> Result<GhRepoInfo, GhRepoInfoError> {
+    ClientBuilder::from_gh_cli()
+        .build_blocking()?
+        .get(owner, repo)
+}
+
+/// Get multiple repositories given `(owner, repo)` pairs, one after
+/// another, reusing a single client.
+///
+/// This is a shorthand for [`Client::new()`] followed by
+/// [`Client::get_many()`].
+pub fn get_many<I>(repos: I) -> Vec<Result<GhRepoInfo, GhRepoInfoError>>
+where
+    I: IntoIterator,
+    I::Item: IntoRepoRef,
+{
+    Client::new().get_many(repos)
+}
+
+/// Check that `token` is valid, returning the user it authenticates as.
+///
+/// This is a shorthand for [`ClientBuilder::token()`] followed by
+/// [`Client::verify_token()`].
+pub fn verify_token(token: impl Into<String>) -> Result<GhAuthInfo, GhRepoInfoError> {
+    ClientBuilder::new()
+        .token(token)
+        .build_blocking()?
+        .verify_token()
+}
+
+/// Iterate the repositories of an organization, fetching
+/// each page lazily as the iterator is advanced.
+///
+/// This is a shorthand for [`Client::new()`] followed by
+/// [`Client::org_repos_iter()`].
+pub fn org_repos_iter(org: impl AsRef<str>, filter: &RepoFilter) -> PaginatedIter<GhRepoInfo> {
+    Client::new().org_repos_iter(org, filter)
+}
+
+/// Blocking equivalent of [`crate::Client`].
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::blocking::Client,
+    api: Api,
+}
+
+impl Client {
+    /// Creates an unauthenticated `Client`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client cannot be initialized.
+    /// Use [`Client::builder()`] to handle the failure instead.
+    pub fn new() -> Self {
+        ClientBuilder::new()
+            .build_blocking()
+            .expect("failed to build client")
+    }
+
+    /// Use [`ClientBuilder::build_blocking()`] to build the `Client`.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    pub(crate) fn from_builder(builder: ClientBuilder) -> Result<Self, GhRepoInfoError> {
+        let mut http = reqwest::blocking::Client::builder().redirect(builder.redirect.to_reqwest());
+        if let Some(user_agent) = &builder.user_agent {
+            http = http.user_agent(user_agent.as_str());
+        }
+        if let Some(timeout) = builder.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(connect_timeout) = builder.connect_timeout {
+            http = http.connect_timeout(connect_timeout);
+        }
+        if builder.http2_prior_knowledge {
+            http = http.http2_prior_knowledge();
+        }
+        if let Some(enabled) = builder.http2_adaptive_window {
+            http = http.http2_adaptive_window(enabled);
+        }
+        if let Some(local_address) = builder.local_address {
+            http = http.local_address(local_address);
+        }
+        for (domain, addrs) in &builder.resolve {
+            http = http.resolve_to_addrs(domain, addrs);
+        }
+        let http = http.build().map_err(GhRepoInfoError::BuildClient)?;
+        Ok(Self {
+            http,
+            api: Api::new(builder),
+        })
+    }
+
+    /// Returns a `Client` sharing the same connection pool, where
+    /// all requests fail with [`GhRepoInfoError::Cancelled`] once
+    /// `token` is cancelled.
+    ///
+    /// The token is checked before each request is sent, including
+    /// between the pages of a listing. Cancelling it also interrupts
+    /// waiting between retries, and waiting for a rate limiter.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        let mut client = self.clone();
+        client.api.cancellation = Some(token);
+        client
+    }
+
+    /// Returns a `Client` sharing the same connection pool, where
+    /// each request uses the given `timeout`, taking precedence over
+    /// [`ClientBuilder::timeout()`].
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        let mut client = self.clone();
+        client.api.timeout = Some(timeout);
+        client
+    }
+
+    /// Returns a `Client` sharing the same connection pool, where
+    /// listings use the given `pagination`, e.g. to only fetch a
+    /// specific page.
+    ///
+    /// Applies to listings paginated using the `Link` header,
+    /// e.g. [`Client::get_forks()`], as well as
+    /// [`Client::get_workflows()`], [`Client::get_environments()`],
+    /// and [`Client::search_repos()`].
+    pub fn with_pagination(&self, pagination: Pagination) -> Self {
+        let mut client = self.clone();
+        client.api.pagination = pagination;
+        client
+    }
+
+    /// Get GitHub repository information given an `owner` and `repo`.
+    pub fn get(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhRepoInfo, GhRepoInfoError> {
+        self.get_json(self.api.repo(owner, repo))
+    }
+
+    /// Build the request [`Client::get()`] would send, without sending it.
+    ///
+    /// Useful for logging and for reproducing requests with other tools.
+    pub fn build_request(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<PreparedRequest, GhRepoInfoError> {
+        let req = self
+            .request(&self.api.repo(owner, repo))
+            .build()
+            .map_err(GhRepoInfoError::from_send)?;
+        Ok(PreparedRequest::new(
+            req.method().clone(),
+            req.url().to_string(),
+            req.headers().clone(),
+            self.api.user_agent.as_ref(),
+        ))
+    }
+
+    /// Revalidate a cached repository using its `ETag`.
+    ///
+    /// If `cached` is `None`, or the repository changed, it is
+    /// replaced with the fetched repository and `true` is returned.
+    /// Otherwise GitHub responds with `304 Not Modified`, `cached`
+    /// is left as is, and `false` is returned. Conditional requests
+    /// answered with `304` do not count against the rate limit.
+    pub fn refresh(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        cached: &mut Option<CachedRepo>,
+    ) -> Result<bool, GhRepoInfoError> {
+        let req = self.api.refresh(owner, repo, cached);
+        let Some(resp) = not_modified_as_none(self.send(&req))? else {
+            return Ok(false);
+        };
+        let etag = etag(resp.headers());
+        let info = json(resp)?;
+        *cached = Some(CachedRepo { info, etag });
+        Ok(true)
+    }
+
+    /// Same as [`Client::get()`], but taking the `owner` and `repo`
+    /// as a single [`IntoRepoRef`], e.g. a tuple.
+    pub fn get_repo(&self, repo: impl IntoRepoRef) -> Result<GhRepoInfo, GhRepoInfoError> {
+        self.get(repo.owner(), repo.repo())
+    }
+
+    /// Get multiple repositories given `(owner, repo)` pairs,
+    /// one after another.
+    ///
+    /// Results are returned in the same order as `repos`, and a
+    /// failure does not affect the rest.
+    pub fn get_many<I>(&self, repos: I) -> Vec<Result<GhRepoInfo, GhRepoInfoError>>
+    where
+        I: IntoIterator,
+        I::Item: IntoRepoRef,
+    {
+        repos.into_iter().map(|repo| self.get_repo(repo)).collect()
+    }
+
+    /// Get multiple repositories given their full names,
+    /// e.g. `"rust-lang/rust"`, one after another.
+    ///
+    /// Results are returned in the same order as `full_names`. Full
+    /// names not of the form `owner/repo` result in
+    /// [`GhRepoInfoError::InvalidFullName`] without affecting the rest.
+    pub fn get_many_full_name<I>(&self, full_names: I) -> Vec<Result<GhRepoInfo, GhRepoInfoError>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        full_names
+            .into_iter()
+            .map(|full_name| {
+                let (owner, repo) = parse_full_name(full_name.as_ref())?;
+                self.get(owner, repo)
+            })
+            .collect()
+    }
+
+    /// Same as [`Client::get_many_full_name()`], but returning the
+    /// results keyed by their full name, as given in `full_names`.
+    ///
+    /// Duplicate full names are only fetched and included once.
+    pub fn get_map<I>(&self, full_names: I) -> HashMap<String, Result<GhRepoInfo, GhRepoInfoError>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let full_names = dedup_full_names(full_names);
+        let results = self.get_many_full_name(&full_names);
+        full_names.into_iter().zip(results).collect()
+    }
+
+    /// Same as [`Client::get()`], but retries failed requests while
+    /// `should_retry(&err, attempt)` returns `true`, where `attempt` is
+    /// the number of attempts made so far, starting at `1`.
+    ///
+    /// The delay between attempts starts at 500 milliseconds and
+    /// doubles for each attempt. [`GhRepoInfoError::Cancelled`] is
+    /// never retried, and cancelling the [`CancellationToken`] during
+    /// the delay returns it immediately.
+    pub fn get_with_retries<F>(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        should_retry: F,
+    ) -> Result<GhRepoInfo, GhRepoInfoError>
+    where
+        F: Fn(&GhRepoInfoError, u32) -> bool,
+    {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        let mut attempt = 1;
+        loop {
+            let err = match self.get_json(self.api.repo(owner, repo)) {
+                Err(err) => err,
+                res => return res,
+            };
+            match retry_after(&err, attempt, &should_retry) {
+                Some(delay) => self.sleep(delay)?,
+                None => return Err(err),
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Same as [`Client::get_with_retries()`] using [`default_should_retry()`].
+    pub fn get_with_default_retries(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhRepoInfo, GhRepoInfoError> {
+        self.get_with_retries(owner, repo, default_should_retry)
+    }
+
+    /// Resolve the current `(owner, repo)` of a repository,
+    /// e.g. after it has been renamed or transferred.
+    ///
+    /// GitHub redirects renamed repositories to `/repositories/{id}`,
+    /// which does not include the name, so the repository itself is
+    /// fetched. Requires the redirect to be followed, i.e. fails with
+    /// [`RedirectPolicy::None`](crate::RedirectPolicy::None).
+    pub fn resolve(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(String, String), GhRepoInfoError> {
+        let info = self.get(owner, repo)?;
+        Ok((info.owner.name, info.name))
+    }
+
+    /// Get the name of the default branch of a repository, e.g. `"main"`.
+    ///
+    /// GitHub has no lighter endpoint for this, so this is the same
+    /// as [`Client::get()`] followed by taking
+    /// [`default_branch`](GhRepoInfo::default_branch).
+    pub fn get_default_branch(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<String, GhRepoInfoError> {
+        let info = self.get(owner, repo)?;
+        Ok(info.default_branch)
+    }
+
+    /// Get the default branch of a repository, including the SHA
+    /// of its latest commit.
+    ///
+    /// This requires two requests, as the name of the default branch
+    /// is first resolved using [`Client::get()`].
+    pub fn get_default_branch_ref(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhBranch, GhRepoInfoError> {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        let info = self.get(owner, repo)?;
+        self.get_json(self.api.branch(owner, repo, &info.default_branch))
+    }
+
+    /// List the git references starting with `ref_prefix`, e.g. `"heads"`
+    /// for all branches, `"tags"` for all tags, or `"heads/feature"`.
+    ///
+    /// Returns [`GhRepoInfoError::EmptyRepository`] if the repository
+    /// has no commits.
+    pub fn get_refs(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        ref_prefix: impl AsRef<str>,
+    ) -> Result<Vec<GhRef>, GhRepoInfoError> {
+        let req = self.api.refs(owner, repo, ref_prefix);
+        conflict_as_empty(self.get_paginated(req))
+    }
+
+    /// Get the number of bytes of code written in each language.
+    ///
+    /// See [`language_percentages()`](crate::language_percentages)
+    /// for converting it into percentages.
+    pub fn get_languages(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<BTreeMap<String, u64>, GhRepoInfoError> {
+        self.get_json(self.api.languages(owner, repo))
+    }
+
+    /// Get both the repository and its languages, see [`Client::get()`]
+    /// and [`Client::get_languages()`].
+    ///
+    /// The requests are sent one after the other, and the first
+    /// error from either is returned.
+    pub fn get_with_languages(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(GhRepoInfo, BTreeMap<String, u64>), GhRepoInfoError> {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        Ok((self.get(owner, repo)?, self.get_languages(owner, repo)?))
+    }
+
+    /// Get the GitHub Pages site of a repository.
+    ///
+    /// Returns `None` if GitHub Pages is not enabled.
+    pub fn get_pages(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Option<GhPagesInfo>, GhRepoInfoError> {
+        not_found_as_none(self.get_json(self.api.pages(owner, repo)))
+    }
+
+    /// Get the views of a repository during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub fn get_traffic_views(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhTrafficViews, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "views"))
+    }
+
+    /// Get the clones of a repository during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub fn get_traffic_clones(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhTrafficClones, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "clones"))
+    }
+
+    /// Get the top 10 referring sites of a repository
+    /// during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub fn get_top_referrers(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhTrafficReferrer>, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "popular/referrers"))
+    }
+
+    /// Get the top 10 popular paths of a repository
+    /// during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub fn get_top_paths(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhTrafficPath>, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "popular/paths"))
+    }
+
+    /// List all repositories the authenticated user has access to,
+    /// including private repositories.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub fn list_my_repos(
+        &self,
+        options: &ListMyReposOptions,
+    ) -> Result<Vec<GhRepoInfo>, GhRepoInfoError> {
+        let mut repos: Vec<GhRepoInfo> = self.get_paginated(self.api.my_repos(options))?;
+        repos.retain(|repo| options.filter.includes(repo));
+        Ok(repos)
+    }
+
+    /// List the public repositories of a `user` whose primary
+    /// language is `language`, compared case-insensitively.
+    ///
+    /// GitHub cannot filter the listing by language, so all pages
+    /// are fetched and filtered afterwards, along with `filter`.
+    pub fn list_user_repos_by_language(
+        &self,
+        user: impl AsRef<str>,
+        language: impl AsRef<str>,
+        filter: &RepoFilter,
+    ) -> Result<Vec<GhRepoInfo>, GhRepoInfoError> {
+        let mut repos = self.get_paginated(self.api.user_repos(user))?;
+        retain_language(&mut repos, language.as_ref(), filter);
+        Ok(repos)
+    }
+
+    /// Search repositories using the given `query`, e.g.
+    /// `"tetris language:rust"`, sorted by best match.
+    ///
+    /// Only a single page is fetched, by default the first page of up
+    /// to 100 results, see [`Client::with_pagination()`].
+    pub fn search_repos(
+        &self,
+        query: impl AsRef<str>,
+    ) -> Result<SearchResults<GhRepoInfo>, GhRepoInfoError> {
+        self.send_json(&self.api.search_repos(query))
+    }
+
+    /// Search repositories tagged with the given `topic`,
+    /// see [`Client::search_repos()`].
+    pub fn search_by_topic(
+        &self,
+        topic: impl AsRef<str>,
+        options: &SearchByTopicOptions,
+    ) -> Result<SearchResults<GhRepoInfo>, GhRepoInfoError> {
+        self.search_repos(options.query(topic.as_ref()))
+    }
+
+    /// List the members of an organization.
+    ///
+    /// Unauthenticated requests only include public members. If the
+    /// authenticated user is a member of the organization, both
+    /// public and private members are included.
+    pub fn list_org_members(
+        &self,
+        org: impl AsRef<str>,
+        options: &ListOrgMembersOptions,
+    ) -> Result<Vec<GhUserRef>, GhRepoInfoError> {
+        self.get_paginated(self.api.org_members(org, options))
+    }
+
+    /// List all labels of a repository.
+    pub fn get_labels(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhLabel>, GhRepoInfoError> {
+        self.get_paginated(self.api.labels(owner, repo))
+    }
+
+    /// List the users who starred a repository, including when
+    /// they starred it, fetching all pages.
+    pub fn get_stargazers(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhStargazer>, GhRepoInfoError> {
+        self.get_paginated(self.api.stargazers(owner, repo))
+    }
+
+    /// List the users watching a repository, fetching all pages.
+    pub fn get_subscribers(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhUserRef>, GhRepoInfoError> {
+        self.get_paginated(self.api.subscribers(owner, repo))
+    }
+
+    /// List the published security advisories of a repository,
+    /// fetching all pages.
+    ///
+    /// If security advisories are not available for the repository,
+    /// e.g. because they are disabled, [`GhRepoInfoError::Forbidden`]
+    /// is returned.
+    pub fn get_security_advisories(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhAdvisory>, GhRepoInfoError> {
+        self.get_paginated(self.api.security_advisories(owner, repo))
+    }
+
+    /// List the forks of a repository, fetching all pages.
+    pub fn get_forks(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        sort: ForkSort,
+    ) -> Result<Vec<GhRepoInfo>, GhRepoInfoError> {
+        self.get_paginated(self.api.forks(owner, repo, sort))
+    }
+
+    /// List the milestones of a repository.
+    pub fn list_milestones(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        options: &ListMilestonesOptions,
+    ) -> Result<Vec<GhMilestone>, GhRepoInfoError> {
+        self.get_paginated(self.api.milestones(owner, repo, options))
+    }
+
+    /// List the deployment environments of a repository.
+    ///
+    /// Only a single page is fetched, by default the first
+    /// 100 environments, see [`Client::with_pagination()`].
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub fn get_environments(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhEnvironment>, GhRepoInfoError> {
+        let environments: GhEnvironments = self.send_json(&self.api.environments(owner, repo))?;
+        Ok(environments.environments)
+    }
+
+    /// Get the GitHub Actions workflows of a repository.
+    ///
+    /// All workflows are returned, including workflows that
+    /// have been disabled, see [`GhWorkflow::state`]. If a page is set
+    /// using [`Client::with_pagination()`], only that page is returned.
+    pub fn get_workflows(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhWorkflow>, GhRepoInfoError> {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        let mut workflows = Vec::new();
+        let mut page = Some(self.api.first_workflows_page());
+        while let Some(current) = page {
+            let resp: GhWorkflows = self.send_json(&self.api.workflows(owner, repo, current))?;
+            let page_len = resp.workflows.len();
+            workflows.extend(resp.workflows);
+            page =
+                self.api
+                    .next_workflows_page(current, page_len, workflows.len(), resp.total_count);
+        }
+        Ok(workflows)
+    }
+
+    /// Get the most recent GitHub Actions workflow run of a repository,
+    /// optionally only for a specific workflow or branch.
+    ///
+    /// Returns `None` if there are no matching runs.
+    pub fn get_latest_run(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        options: &LatestRunOptions,
+    ) -> Result<Option<GhWorkflowRun>, GhRepoInfoError> {
+        let runs: GhWorkflowRuns = self.send_json(&self.api.latest_run(owner, repo, options))?;
+        Ok(runs.workflow_runs.into_iter().next())
+    }
+
+    /// Get the recent events of a repository, most recent first.
+    ///
+    /// GitHub only includes events from the past 90 days,
+    /// and at most 300 events.
+    pub fn get_events(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhEvent>, GhRepoInfoError> {
+        self.get_paginated(self.api.events(owner, repo))
+    }
+
+    /// Iterate the repositories of an organization, fetching
+    /// each page lazily as the iterator is advanced.
+    ///
+    /// Repositories excluded by `filter` are skipped.
+    pub fn org_repos_iter(
+        &self,
+        org: impl AsRef<str>,
+        filter: &RepoFilter,
+    ) -> PaginatedIter<GhRepoInfo> {
+        let filter = *filter;
+        PaginatedIter::new(self.clone(), self.api.org_repos(org))
+            .retain(move |repo| filter.includes(repo))
+    }
+
+    /// Get the number of stars of each of the given `(owner, repo)`
+    /// pairs, using a single GraphQL query,
+    /// see [`graphql::get_stars_bulk()`](crate::graphql::get_stars_bulk).
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    #[cfg(feature = "graphql")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "graphql")))]
+    pub fn get_stars_bulk(
+        &self,
+        repos: &[(String, String)],
+    ) -> Result<BTreeMap<String, usize>, GhRepoInfoError> {
+        use super::graphql::{stars_from_data, GraphQlResponse, StarsData};
+
+        let req = self.api.stars_bulk(repos);
+        self.api.check(&req)?;
+        if repos.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let resp: GraphQlResponse<StarsData> = self.send_json(&req)?;
+        Ok(stars_from_data(resp.into_data()?))
+    }
+
+    /// Returns `true` if the authenticated user has starred the repository.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub fn is_starred(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<bool, GhRepoInfoError> {
+        // Responds with an empty `204 No Content` if starred,
+        // so the body must not be deserialized
+        let req = self.api.starred(Method::GET, owner, repo);
+        let resp = not_found_as_none(self.send(&req))?;
+        Ok(resp.is_some())
+    }
+
+    /// Star a repository for the authenticated user.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub fn star(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(), GhRepoInfoError> {
+        self.send(&self.api.starred(Method::PUT, owner, repo))?;
+        Ok(())
+    }
+
+    /// Unstar a repository for the authenticated user.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub fn unstar(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(), GhRepoInfoError> {
+        self.send(&self.api.starred(Method::DELETE, owner, repo))?;
+        Ok(())
+    }
+
+    /// Get the full profile of a repository owner, using
+    /// `/orgs/{org}` or `/users/{user}` depending on its kind.
+    pub fn get_owner_profile(
+        &self,
+        owner: &GhRepoOwnerInfo,
+    ) -> Result<GhOwnerProfile, GhRepoInfoError> {
+        self.get_json(self.api.owner_profile(owner))
+    }
+
+    /// Check that the token is valid, returning the user it
+    /// authenticates as, e.g. before starting a long running job.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. An invalid token results in
+    /// [`GhRepoInfoError::Unauthorized`], or
+    /// [`GhRepoInfoError::TokenExpired`].
+    pub fn verify_token(&self) -> Result<GhAuthInfo, GhRepoInfoError> {
+        let resp = self.send(&self.api.user())?;
+        let scopes = oauth_scopes(resp.headers());
+        let user: GhUserRef = json(resp)?;
+        Ok(GhAuthInfo {
+            login: user.name,
+            scopes,
+        })
+    }
+
+    /// Create an access token for a GitHub App installation, which
+    /// can then be passed to [`ClientBuilder::token()`].
+    ///
+    /// `jwt` is a JSON Web Token signed with the private key of the
+    /// GitHub App, which is sent instead of the token of the `Client`.
+    pub fn create_installation_token(
+        &self,
+        jwt: impl AsRef<str>,
+        installation_id: u64,
+    ) -> Result<GhInstallationToken, GhRepoInfoError> {
+        let req = self.api.installation_token(jwt.as_ref(), installation_id);
+        self.send_json(&req)
+    }
+
+    /// Download the avatar image of a user or organization.
+    ///
+    /// If `size` is given, GitHub scales the image to be `size`
+    /// pixels wide and tall. The token is not sent, as avatars
+    /// are served from a different host.
+    pub fn fetch_avatar(
+        &self,
+        owner: &GhRepoOwnerInfo,
+        size: Option<u32>,
+    ) -> Result<Vec<u8>, GhRepoInfoError> {
+        let resp = self.send(&self.api.avatar(owner, size))?;
+        let bytes = resp.bytes().map_err(GhRepoInfoError::from_send)?;
+        Ok(bytes.to_vec())
+    }
+
+    fn request(&self, req: &ApiRequest) -> RequestBuilder {
+        let mut builder = self
+            .http
+            .request(req.method.clone(), &req.url)
+            .query(&req.query);
+        for (name, value) in &req.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(token) = &req.bearer {
+            builder = builder.bearer_auth(token);
+        }
+        #[cfg(feature = "graphql")]
+        if let Some(graphql) = &req.graphql {
+            builder = builder.json(graphql);
+        }
+        match self.api.timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
+    }
+
+    fn send(&self, req: &ApiRequest) -> Result<Response, GhRepoInfoError> {
+        self.api.check(req)?;
+        if let Some(wait) = self.api.rate_limit() {
+            self.sleep(wait)?;
+        }
+
+        let req = self
+            .request(req)
+            .build()
+            .map_err(GhRepoInfoError::from_send)?;
+        let url = req.url().to_string();
+
+        let start = Instant::now();
+        let resp = self.http.execute(req);
+        match &resp {
+            Ok(resp) => self
+                .api
+                .on_request(url, Some(resp.status()), Some(resp.headers()), start),
+            Err(err) => self.api.on_request(url, err.status(), None, start),
+        }
+
+        let resp = resp.map_err(GhRepoInfoError::from_send)?;
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            let headers = resp.headers().clone();
+            let body = resp.bytes().unwrap_or_default();
+            return Err(unauthorized(&headers, &body));
+        }
+        check_status(resp.status(), resp.headers())?;
+        Ok(resp)
+    }
+
+    /// Blocks the current thread for `duration`, failing with
+    /// [`GhRepoInfoError::Cancelled`] as soon as the `Client`
+    /// is cancelled.
+    fn sleep(&self, duration: Duration) -> Result<(), GhRepoInfoError> {
+        self.api.check_cancelled()?;
+        match &self.api.cancellation {
+            Some(cancellation) if cancellation.wait_timeout(duration) => {
+                Err(GhRepoInfoError::Cancelled)
+            }
+            Some(_cancellation) => Ok(()),
+            None => {
+                thread::sleep(duration);
+                Ok(())
+            }
+        }
+    }
+
+    fn send_json<T>(&self, req: &ApiRequest) -> Result<T, GhRepoInfoError>
+    where
+        T: DeserializeOwned,
+    {
+        json(self.send(req)?)
+    }
+
+    /// Same as [`Client::send_json()`], but using the [`ResponseCache`]
+    /// of the `Client`, if any.
+    fn get_json<T>(&self, req: ApiRequest) -> Result<T, GhRepoInfoError>
+    where
+        T: DeserializeOwned,
+    {
+        let Some(cache) = &self.api.cache else {
+            return self.send_json(&req);
+        };
+
+        let (req, cached) = self.api.revalidate(cache, req);
+        let resp = match revalidated(self.send(&req), cached)? {
+            ControlFlow::Continue(resp) => resp,
+            ControlFlow::Break(value) => return Ok(value),
+        };
+        check_content_type(resp.headers())?;
+
+        let request_id = request_id(resp.headers());
+        let etag = etag(resp.headers());
+        let body = resp.bytes().map_err(GhRepoInfoError::from_send)?;
+        store(cache, &req.url, etag, request_id, &body)
+    }
+
+    /// Fetches all pages by following the `Link` header.
+    fn get_paginated<T>(&self, req: ApiRequest) -> Result<Vec<T>, GhRepoInfoError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        PaginatedIter::new(self.clone(), req).collect()
+    }
+}
+
+fn json<T>(resp: Response) -> Result<T, GhRepoInfoError>
+where
+    T: DeserializeOwned,
+{
+    check_content_type(resp.headers())?;
+    let request_id = request_id(resp.headers());
+    resp.json::<T>()
+        .map_err(|err| GhRepoInfoError::from_deserialize(err, request_id))
+}
+
+/// Same as [`json()`], but deserializes directly from the response,
+/// instead of first buffering the raw bytes of the whole body. The
+/// value is still deserialized as a whole, e.g. an entire page, see
+/// [`json_items()`] for deserializing each element of a page.
+fn json_streaming<T>(resp: Response) -> Result<T, GhRepoInfoError>
+where
+    T: DeserializeOwned,
+{
+    check_content_type(resp.headers())?;
+    let request_id = request_id(resp.headers());
+    let mut body = BodyReader { resp, err: None };
+    serde_json::from_reader(BufReader::new(&mut body))
+        .map_err(|err| body_error(err, &mut body, request_id))
+}
+
+/// Deserializes the elements of the JSON array body of `resp` on
+/// another thread, sending each element as soon as it is parsed,
+/// see [`ClientBuilder::stream_listings()`].
+fn json_items<T>(resp: Response) -> Result<Receiver<Result<T, GhRepoInfoError>>, GhRepoInfoError>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    check_content_type(resp.headers())?;
+    let request_id = request_id(resp.headers());
+    // Without a buffer, the next element is only parsed
+    // once the previous element has been received
+    let (tx, rx) = mpsc::sync_channel(0);
+    thread::spawn(move || {
+        let mut body = BodyReader { resp, err: None };
+        let res = {
+            let mut de = serde_json::Deserializer::from_reader(BufReader::new(&mut body));
+            de.deserialize_seq(SendItems(tx.clone()))
+                .and_then(|()| de.end())
+        };
+        if let Err(err) = res {
+            let _ = tx.send(Err(body_error(err, &mut body, request_id)));
+        }
+    });
+    Ok(rx)
+}
+
+/// Returns the error of failing to deserialize a response read
+/// using `body`.
+fn body_error(
+    err: serde_json::Error,
+    body: &mut BodyReader,
+    request_id: Option<String>,
+) -> GhRepoInfoError {
+    match body.err.take() {
+        // Reading the body failed, e.g. timed out, which `serde_json`
+        // only reports as an I/O error
+        Some(err) => GhRepoInfoError::from_send(err),
+        None => GhRepoInfoError::DeserializeJsonFailed(err, request_id),
+    }
+}
+
+/// Visits the elements of a JSON array, sending each element
+/// as soon as it is deserialized.
+struct SendItems<T>(SyncSender<Result<T, GhRepoInfoError>>);
+
+impl<'de, T> Visitor<'de> for SendItems<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while let Some(item) = seq.next_element()? {
+            if self.0.send(Ok(item)).is_err() {
+                return Err(de::Error::custom("the iterator was dropped"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the body of a [`Response`], keeping the [`reqwest::Error`]
+/// of a failed read, e.g. a timeout.
+struct BodyReader {
+    resp: Response,
+    err: Option<reqwest::Error>,
+}
+
+impl Read for BodyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.resp.read(buf).map_err(|err| {
+            if !err
+                .get_ref()
+                .is_some_and(|inner| inner.is::<reqwest::Error>())
+            {
+                return err;
+            }
+            let kind = err.kind();
+            if let Some(Ok(inner)) = err.into_inner().map(|inner| inner.downcast()) {
+                self.err = Some(*inner);
+            }
+            io::Error::from(kind)
+        })
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type Retain<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// Iterator over the items of a paginated listing, requesting the
+/// next page by following the `Link` header once the current page
+/// is exhausted.
+///
+/// After yielding an error, the iterator is exhausted.
+pub struct PaginatedIter<T> {
+    client: Client,
+    next: Option<ApiRequest>,
+    page: Page<T>,
+    retain: Option<Retain<T>>,
+}
+
+/// The items of the current page of a [`PaginatedIter`].
+#[derive(Debug)]
+enum Page<T> {
+    Buffered(vec::IntoIter<T>),
+    /// See [`json_items()`].
+    Streamed(Receiver<Result<T, GhRepoInfoError>>),
+}
+
+impl<T> Page<T> {
+    fn next(&mut self) -> Option<Result<T, GhRepoInfoError>> {
+        match self {
+            Self::Buffered(items) => items.next().map(Ok),
+            Self::Streamed(items) => items.recv().ok(),
+        }
+    }
+}
+
+impl<T> PaginatedIter<T> {
+    fn new(client: Client, req: ApiRequest) -> Self {
+        Self {
+            client,
+            next: Some(req),
+            page: Page::Buffered(Vec::new().into_iter()),
+            retain: None,
+        }
+    }
+
+    /// Only yield the items for which `f` returns `true`.
+    fn retain<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.retain = Some(Box::new(f));
+        self
+    }
+}
+
+impl<T> fmt::Debug for PaginatedIter<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaginatedIter")
+            .field("client", &self.client)
+            .field("next", &self.next.as_ref().map(|req| &req.url))
+            .field("page", &self.page)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Iterator for PaginatedIter<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    type Item = Result<T, GhRepoInfoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.page.next() {
+                Some(Ok(item)) => {
+                    if let Some(retain) = &self.retain {
+                        if !retain(&item) {
+                            continue;
+                        }
+                    }
+                    return Some(Ok(item));
+                }
+                Some(Err(err)) => {
+                    self.next = None;
+                    return Some(Err(err));
+                }
+                None => {}
+            }
+
+            let req = self.next.take()?;
+            let resp = match self.client.send(&req) {
+                Ok(resp) => resp,
+                Err(err) => return Some(Err(err)),
+            };
+
+            self.next = self.client.api.next_page(&req, resp.headers());
+
+            let page = if self.client.api.stream_listings {
+                json_items(resp).map(Page::Streamed)
+            } else {
+                json_streaming::<Vec<T>>(resp).map(|page| Page::Buffered(page.into_iter()))
+            };
+            match page {
+                Ok(page) => self.page = page,
+                Err(err) => {
+                    self.next = None;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::test_util::{repo_ok, serve, serve_sequence, serve_stalled};
+    use crate::tests::repo_json;
+
+    fn client(base_url: String) -> Client {
+        ClientBuilder::new()
+            .token("ghp_test")
+            .base_url(base_url)
+            .build_blocking()
+            .unwrap()
+    }
+
+    #[test]
+    fn is_starred_no_content() {
+        let base_url = serve("HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n");
+        let res = client(base_url).is_starred("rust-lang", "rust");
+        assert!(matches!(res, Ok(true)), "{res:?}");
+    }
+
+    #[test]
+    fn is_starred_not_found() {
+        let base_url = serve(concat!(
+            "HTTP/1.1 404 Not Found\r\n",
+            "Content-Type: application/json\r\n",
+            "Content-Length: 23\r\n",
+            "Connection: close\r\n",
+            "\r\n",
+            r#"{"message":"Not Found"}"#,
+        ));
+        let res = client(base_url).is_starred("rust-lang", "rust");
+        assert!(matches!(res, Ok(false)), "{res:?}");
+    }
+
+    #[test]
+    fn get_map_dedups() {
+        let (base_url, requests) = serve_sequence(vec![repo_ok(), repo_ok()]);
+        let repos = client(base_url).get_map(["rust-lang/rust", "rust-lang/rust"]);
+        assert_eq!(repos.len(), 1);
+        assert_eq!(
+            repos["rust-lang/rust"].as_ref().unwrap().full_name,
+            "rust-lang/rust"
+        );
+        assert_eq!(requests.try_iter().count(), 1);
+    }
+
+    /// Names of the public methods in the `impl Client` block of `src`.
+    fn client_methods(src: &str) -> BTreeSet<&str> {
+        let start = src.find("\nimpl Client {\n").unwrap();
+        let end = start + src[start..].find("\n}\n").unwrap();
+        src[start..end]
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_start();
+                let line = line.strip_prefix("pub ")?;
+                let line = line.strip_prefix("async ").unwrap_or(line);
+                let name = line.strip_prefix("fn ")?;
+                name.split(['(', '<']).next()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn client_parity() {
+        let mut blocking = client_methods(include_str!("blocking.rs"));
+        let mut async_client = client_methods(include_str!("client.rs"));
+        assert!(blocking.remove("org_repos_iter"));
+        assert!(async_client.remove("org_repos_stream"));
+        assert_eq!(blocking, async_client);
+    }
+
+    #[test]
+    fn stream_listings_yields_before_end_of_page() {
+        let base_url = serve_stalled(format!(
+            concat!(
+                "HTTP/1.1 200 OK\r\n",
+                "Content-Type: application/json\r\n",
+                "Content-Length: 100000\r\n",
+                "\r\n",
+                "[{},",
+            ),
+            repo_json(),
+        ));
+        let client = ClientBuilder::new()
+            .base_url(base_url)
+            .timeout(Duration::from_secs(5))
+            .stream_listings(true)
+            .build_blocking()
+            .unwrap();
+
+        let mut repos = client.org_repos_iter("rust-lang", &RepoFilter::default());
+        let repo = repos.next().unwrap().unwrap();
+        assert_eq!(repo.full_name, "rust-lang/rust");
+    }
+
+    #[test]
+    fn cancel_during_retry_delay() {
+        let base_url = serve(concat!(
+            "HTTP/1.1 500 Internal Server Error\r\n",
+            "Content-Length: 0\r\n",
+            "Connection: close\r\n",
+            "\r\n",
+        ));
+        let cancellation = CancellationToken::new();
+        let client = client(base_url).with_cancellation(cancellation.clone());
+
+        // The delay after the second attempt is 1 second
+        let start = Instant::now();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(700));
+            cancellation.cancel();
+        });
+        let res = client.get_with_retries("rust-lang", "rust", |_err, _attempt| true);
+
+        assert!(matches!(res, Err(GhRepoInfoError::Cancelled)), "{res:?}");
+        assert!(start.elapsed() < Duration::from_millis(1200));
+    }
+
+    #[test]
+    fn body_timeout_is_timeout() {
+        let base_url = serve_stalled(concat!(
+            "HTTP/1.1 200 OK\r\n",
+            "Content-Type: application/json\r\n",
+            "Content-Length: 100\r\n",
+            "\r\n",
+            "[",
+        ));
+        let client = ClientBuilder::new()
+            .base_url(base_url)
+            .timeout(Duration::from_millis(200))
+            .build_blocking()
+            .unwrap();
+
+        let res = client.get_events("rust-lang", "rust");
+        assert!(matches!(res, Err(GhRepoInfoError::Timeout(_))), "{res:?}");
+    }
+}
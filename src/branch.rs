@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhBranch {
+    pub name: String,
+    pub commit: GhCommitRef,
+    #[serde(rename = "protected")]
+    pub is_protected: bool,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhCommitRef {
+    pub sha: String,
+}
@@ -0,0 +1,15 @@
+use super::GhRepoInfo;
+
+/// A previously fetched repository together with its `ETag`.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::refresh()`](crate::Client::refresh)."
+)]
+#[derive(Clone, Debug)]
+pub struct CachedRepo {
+    pub info: GhRepoInfo,
+    /// `None` if the response did not include an `ETag` header,
+    /// in which case the repository is always fetched again.
+    pub etag: Option<String>,
+}
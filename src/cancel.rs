@@ -0,0 +1,55 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+#[cfg(feature = "blocking")]
+use std::time::Duration;
+
+/// Token for cancelling requests made by a [`Client`](crate::Client)
+/// returned by [`Client::with_cancellation()`](crate::Client::with_cancellation).
+///
+/// Clones share the same state, so cancelling one cancels all of them.
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default, Debug)]
+struct Inner {
+    cancelled: Mutex<bool>,
+    /// Notified when cancelled, waking up blocking waits.
+    condvar: Condvar,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        let mut cancelled = self.lock();
+        *cancelled = true;
+        self.inner.condvar.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.lock()
+    }
+
+    /// Blocks the current thread for `timeout`, or until cancelled,
+    /// returning whether it was cancelled.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn wait_timeout(&self, timeout: Duration) -> bool {
+        let cancelled = self.lock();
+        let (cancelled, _timeout) = self
+            .inner
+            .condvar
+            .wait_timeout_while(cancelled, timeout, |cancelled| !*cancelled)
+            .unwrap_or_else(PoisonError::into_inner);
+        *cancelled
+    }
+
+    fn lock(&self) -> MutexGuard<'_, bool> {
+        self.inner
+            .cancelled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
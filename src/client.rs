@@ -0,0 +1,1780 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+use std::vec;
+
+use futures_util::future::{self, join_all, try_join};
+use futures_util::stream::{self, Stream, TryStreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+use super::api::{
+    conflict_as_empty, dedup_full_names, etag, not_found_as_none, not_modified_as_none,
+    retain_language, retry_after, revalidated, store, Api, ApiRequest,
+};
+use super::environment::GhEnvironments;
+use super::gh_auth::{gh_api_url, gh_cli_token};
+use super::json_array::JsonArrayParser;
+use super::metrics::{MetricsHook, RequestMetrics};
+use super::response_cache::{CacheHandle, ResponseCache};
+use super::workflow::{GhWorkflowRuns, GhWorkflows};
+use super::{
+    check_content_type, check_status, default_should_retry, oauth_scopes, parse_full_name,
+    request_id, unauthorized, CachedRepo, CancellationToken, GhAdvisory, GhAuthInfo, GhBranch,
+    GhEnvironment, GhEvent, GhInstallationToken, GhLabel, GhMilestone, GhOwnerProfile, GhPagesInfo,
+    GhRef, GhRepoInfo, GhRepoInfoError, GhRepoOwnerInfo, GhStargazer, GhTrafficClones,
+    GhTrafficPath, GhTrafficReferrer, GhTrafficViews, GhUserRef, GhWorkflow, GhWorkflowRun,
+    IntoRepoRef, Pagination, RateLimiter, SearchResults, API_URL,
+};
+
+/// Client for the GitHub REST API.
+///
+/// Reuse a single `Client` when making multiple requests,
+/// as it holds a connection pool internally.
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    api: Api,
+}
+
+impl Client {
+    /// Creates an unauthenticated `Client`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client cannot be initialized.
+    /// Use [`Client::builder()`] to handle the failure instead.
+    pub fn new() -> Self {
+        ClientBuilder::new()
+            .build()
+            .expect("failed to build client")
+    }
+
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Returns a `Client` sharing the same connection pool, where
+    /// all requests fail with [`GhRepoInfoError::Cancelled`] once
+    /// `token` is cancelled.
+    ///
+    /// The token is checked before each request is sent, including
+    /// between the pages of a listing.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        let mut client = self.clone();
+        client.api.cancellation = Some(token);
+        client
+    }
+
+    /// Returns a `Client` sharing the same connection pool, where
+    /// each request uses the given `timeout`, taking precedence over
+    /// [`ClientBuilder::timeout()`].
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        let mut client = self.clone();
+        client.api.timeout = Some(timeout);
+        client
+    }
+
+    /// Returns a `Client` sharing the same connection pool, where
+    /// listings use the given `pagination`, e.g. to only fetch a
+    /// specific page.
+    ///
+    /// Applies to listings paginated using the `Link` header,
+    /// e.g. [`Client::get_forks()`], as well as
+    /// [`Client::get_workflows()`], [`Client::get_environments()`],
+    /// and [`Client::search_repos()`].
+    pub fn with_pagination(&self, pagination: Pagination) -> Self {
+        let mut client = self.clone();
+        client.api.pagination = pagination;
+        client
+    }
+
+    /// Get GitHub repository information given an `owner` and `repo`.
+    pub async fn get(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhRepoInfo, GhRepoInfoError> {
+        self.get_json(self.api.repo(owner, repo)).await
+    }
+
+    /// Build the request [`Client::get()`] would send, without sending it.
+    ///
+    /// Useful for logging and for reproducing requests with other tools.
+    pub fn build_request(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<PreparedRequest, GhRepoInfoError> {
+        let req = self
+            .request(&self.api.repo(owner, repo))
+            .build()
+            .map_err(GhRepoInfoError::from_send)?;
+        Ok(PreparedRequest::new(
+            req.method().clone(),
+            req.url().to_string(),
+            req.headers().clone(),
+            self.api.user_agent.as_ref(),
+        ))
+    }
+
+    /// Revalidate a cached repository using its `ETag`.
+    ///
+    /// If `cached` is `None`, or the repository changed, it is
+    /// replaced with the fetched repository and `true` is returned.
+    /// Otherwise GitHub responds with `304 Not Modified`, `cached`
+    /// is left as is, and `false` is returned. Conditional requests
+    /// answered with `304` do not count against the rate limit.
+    pub async fn refresh(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        cached: &mut Option<CachedRepo>,
+    ) -> Result<bool, GhRepoInfoError> {
+        let req = self.api.refresh(owner, repo, cached);
+        let Some(resp) = not_modified_as_none(self.send(&req).await)? else {
+            return Ok(false);
+        };
+        let etag = etag(resp.headers());
+        let info = json(resp).await?;
+        *cached = Some(CachedRepo { info, etag });
+        Ok(true)
+    }
+
+    /// Same as [`Client::get()`], but taking the `owner` and `repo`
+    /// as a single [`IntoRepoRef`], e.g. a tuple.
+    pub async fn get_repo(&self, repo: impl IntoRepoRef) -> Result<GhRepoInfo, GhRepoInfoError> {
+        self.get(repo.owner(), repo.repo()).await
+    }
+
+    /// Get multiple repositories concurrently given `(owner, repo)` pairs.
+    ///
+    /// Results are returned in the same order as `repos`, and a
+    /// failure does not affect the rest.
+    ///
+    /// All requests are sent at once, consider using a
+    /// [`RateLimiter`] for large numbers of repositories.
+    pub async fn get_many<I>(&self, repos: I) -> Vec<Result<GhRepoInfo, GhRepoInfoError>>
+    where
+        I: IntoIterator,
+        I::Item: IntoRepoRef,
+    {
+        join_all(
+            repos
+                .into_iter()
+                .map(|repo| async move { self.get_repo(repo).await }),
+        )
+        .await
+    }
+
+    /// Get multiple repositories concurrently given their full names,
+    /// e.g. `"rust-lang/rust"`.
+    ///
+    /// Results are returned in the same order as `full_names`. Full
+    /// names not of the form `owner/repo` result in
+    /// [`GhRepoInfoError::InvalidFullName`] without affecting the rest.
+    ///
+    /// All requests are sent at once, consider using a
+    /// [`RateLimiter`] for large numbers of repositories.
+    pub async fn get_many_full_name<I>(
+        &self,
+        full_names: I,
+    ) -> Vec<Result<GhRepoInfo, GhRepoInfoError>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let futs = full_names.into_iter().map(|full_name| {
+            let parsed = parse_full_name(full_name.as_ref())
+                .map(|(owner, repo)| (owner.to_owned(), repo.to_owned()));
+            async move {
+                let (owner, repo) = parsed?;
+                self.get(owner, repo).await
+            }
+        });
+        join_all(futs).await
+    }
+
+    /// Same as [`Client::get_many_full_name()`], but returning the
+    /// results keyed by their full name, as given in `full_names`.
+    ///
+    /// Duplicate full names are only fetched and included once.
+    pub async fn get_map<I>(
+        &self,
+        full_names: I,
+    ) -> HashMap<String, Result<GhRepoInfo, GhRepoInfoError>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let full_names = dedup_full_names(full_names);
+        let results = self.get_many_full_name(&full_names).await;
+        full_names.into_iter().zip(results).collect()
+    }
+
+    /// Same as [`Client::get()`], but retries failed requests while
+    /// `should_retry(&err, attempt)` returns `true`, where `attempt` is
+    /// the number of attempts made so far, starting at `1`.
+    ///
+    /// The delay between attempts starts at 500 milliseconds and
+    /// doubles for each attempt. Dropping the future during the delay
+    /// cancels any remaining attempts, and [`GhRepoInfoError::Cancelled`]
+    /// is never retried.
+    pub async fn get_with_retries<F>(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        should_retry: F,
+    ) -> Result<GhRepoInfo, GhRepoInfoError>
+    where
+        F: Fn(&GhRepoInfoError, u32) -> bool,
+    {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        let mut attempt = 1;
+        loop {
+            let err = match self.get_json(self.api.repo(owner, repo)).await {
+                Err(err) => err,
+                res => return res,
+            };
+            match retry_after(&err, attempt, &should_retry) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(err),
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Same as [`Client::get_with_retries()`] using [`default_should_retry()`].
+    pub async fn get_with_default_retries(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhRepoInfo, GhRepoInfoError> {
+        self.get_with_retries(owner, repo, default_should_retry)
+            .await
+    }
+
+    /// Resolve the current `(owner, repo)` of a repository,
+    /// e.g. after it has been renamed or transferred.
+    ///
+    /// GitHub redirects renamed repositories to `/repositories/{id}`,
+    /// which does not include the name, so the repository itself is
+    /// fetched. Requires the redirect to be followed, i.e. fails with
+    /// [`RedirectPolicy::None`].
+    pub async fn resolve(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(String, String), GhRepoInfoError> {
+        let info = self.get(owner, repo).await?;
+        Ok((info.owner.name, info.name))
+    }
+
+    /// Get the name of the default branch of a repository, e.g. `"main"`.
+    ///
+    /// GitHub has no lighter endpoint for this, so this is the same
+    /// as [`Client::get()`] followed by taking
+    /// [`default_branch`](GhRepoInfo::default_branch).
+    pub async fn get_default_branch(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<String, GhRepoInfoError> {
+        let info = self.get(owner, repo).await?;
+        Ok(info.default_branch)
+    }
+
+    /// Get the default branch of a repository, including the SHA
+    /// of its latest commit.
+    ///
+    /// This requires two requests, as the name of the default branch
+    /// is first resolved using [`Client::get()`].
+    pub async fn get_default_branch_ref(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhBranch, GhRepoInfoError> {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        let info = self.get(owner, repo).await?;
+        self.get_json(self.api.branch(owner, repo, &info.default_branch))
+            .await
+    }
+
+    /// List the git references starting with `ref_prefix`, e.g. `"heads"`
+    /// for all branches, `"tags"` for all tags, or `"heads/feature"`.
+    ///
+    /// Returns [`GhRepoInfoError::EmptyRepository`] if the repository
+    /// has no commits.
+    pub async fn get_refs(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        ref_prefix: impl AsRef<str>,
+    ) -> Result<Vec<GhRef>, GhRepoInfoError> {
+        let req = self.api.refs(owner, repo, ref_prefix);
+        conflict_as_empty(self.get_paginated(req).await)
+    }
+
+    /// Get the number of bytes of code written in each language.
+    ///
+    /// See [`language_percentages()`](crate::language_percentages)
+    /// for converting it into percentages.
+    pub async fn get_languages(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<BTreeMap<String, u64>, GhRepoInfoError> {
+        self.get_json(self.api.languages(owner, repo)).await
+    }
+
+    /// Get both the repository and its languages, see [`Client::get()`]
+    /// and [`Client::get_languages()`].
+    ///
+    /// Both requests are sent concurrently, and the first error
+    /// from either is returned.
+    pub async fn get_with_languages(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(GhRepoInfo, BTreeMap<String, u64>), GhRepoInfoError> {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        try_join(self.get(owner, repo), self.get_languages(owner, repo)).await
+    }
+
+    /// Get the GitHub Pages site of a repository.
+    ///
+    /// Returns `None` if GitHub Pages is not enabled.
+    pub async fn get_pages(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Option<GhPagesInfo>, GhRepoInfoError> {
+        not_found_as_none(self.get_json(self.api.pages(owner, repo)).await)
+    }
+
+    /// Get the views of a repository during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub async fn get_traffic_views(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhTrafficViews, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "views")).await
+    }
+
+    /// Get the clones of a repository during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub async fn get_traffic_clones(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<GhTrafficClones, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "clones")).await
+    }
+
+    /// Get the top 10 referring sites of a repository
+    /// during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub async fn get_top_referrers(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhTrafficReferrer>, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "popular/referrers"))
+            .await
+    }
+
+    /// Get the top 10 popular paths of a repository
+    /// during the last 14 days.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. The authenticated user must have push access,
+    /// otherwise [`GhRepoInfoError::Forbidden`] is returned.
+    pub async fn get_top_paths(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhTrafficPath>, GhRepoInfoError> {
+        self.get_json(self.api.traffic(owner, repo, "popular/paths"))
+            .await
+    }
+
+    /// List all repositories the authenticated user has access to,
+    /// including private repositories.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub async fn list_my_repos(
+        &self,
+        options: &ListMyReposOptions,
+    ) -> Result<Vec<GhRepoInfo>, GhRepoInfoError> {
+        let mut repos: Vec<GhRepoInfo> = self.get_paginated(self.api.my_repos(options)).await?;
+        repos.retain(|repo| options.filter.includes(repo));
+        Ok(repos)
+    }
+
+    /// List the public repositories of a `user` whose primary
+    /// language is `language`, compared case-insensitively.
+    ///
+    /// GitHub cannot filter the listing by language, so all pages
+    /// are fetched and filtered afterwards, along with `filter`.
+    pub async fn list_user_repos_by_language(
+        &self,
+        user: impl AsRef<str>,
+        language: impl AsRef<str>,
+        filter: &RepoFilter,
+    ) -> Result<Vec<GhRepoInfo>, GhRepoInfoError> {
+        let mut repos = self.get_paginated(self.api.user_repos(user)).await?;
+        retain_language(&mut repos, language.as_ref(), filter);
+        Ok(repos)
+    }
+
+    /// Stream the repositories of an organization, fetching
+    /// each page lazily as the stream is polled.
+    ///
+    /// Repositories excluded by `filter` are skipped. After
+    /// yielding an error, the stream ends.
+    pub fn org_repos_stream(
+        &self,
+        org: impl AsRef<str>,
+        filter: &RepoFilter,
+    ) -> impl Stream<Item = Result<GhRepoInfo, GhRepoInfoError>> + '_ {
+        let filter = *filter;
+        self.paginate(self.api.org_repos(org))
+            .try_filter(move |repo| future::ready(filter.includes(repo)))
+    }
+
+    /// Search repositories using the given `query`, e.g.
+    /// `"tetris language:rust"`, sorted by best match.
+    ///
+    /// Only a single page is fetched, by default the first page of up
+    /// to 100 results, see [`Client::with_pagination()`].
+    pub async fn search_repos(
+        &self,
+        query: impl AsRef<str>,
+    ) -> Result<SearchResults<GhRepoInfo>, GhRepoInfoError> {
+        self.send_json(&self.api.search_repos(query)).await
+    }
+
+    /// Search repositories tagged with the given `topic`,
+    /// see [`Client::search_repos()`].
+    pub async fn search_by_topic(
+        &self,
+        topic: impl AsRef<str>,
+        options: &SearchByTopicOptions,
+    ) -> Result<SearchResults<GhRepoInfo>, GhRepoInfoError> {
+        self.search_repos(options.query(topic.as_ref())).await
+    }
+
+    /// List the members of an organization.
+    ///
+    /// Unauthenticated requests only include public members. If the
+    /// authenticated user is a member of the organization, both
+    /// public and private members are included.
+    pub async fn list_org_members(
+        &self,
+        org: impl AsRef<str>,
+        options: &ListOrgMembersOptions,
+    ) -> Result<Vec<GhUserRef>, GhRepoInfoError> {
+        self.get_paginated(self.api.org_members(org, options)).await
+    }
+
+    /// List all labels of a repository.
+    pub async fn get_labels(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhLabel>, GhRepoInfoError> {
+        self.get_paginated(self.api.labels(owner, repo)).await
+    }
+
+    /// List the users who starred a repository, including when
+    /// they starred it, fetching all pages.
+    pub async fn get_stargazers(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhStargazer>, GhRepoInfoError> {
+        self.get_paginated(self.api.stargazers(owner, repo)).await
+    }
+
+    /// List the users watching a repository, fetching all pages.
+    pub async fn get_subscribers(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhUserRef>, GhRepoInfoError> {
+        self.get_paginated(self.api.subscribers(owner, repo)).await
+    }
+
+    /// List the published security advisories of a repository,
+    /// fetching all pages.
+    ///
+    /// If security advisories are not available for the repository,
+    /// e.g. because they are disabled, [`GhRepoInfoError::Forbidden`]
+    /// is returned.
+    pub async fn get_security_advisories(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhAdvisory>, GhRepoInfoError> {
+        self.get_paginated(self.api.security_advisories(owner, repo))
+            .await
+    }
+
+    /// List the forks of a repository, fetching all pages.
+    pub async fn get_forks(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        sort: ForkSort,
+    ) -> Result<Vec<GhRepoInfo>, GhRepoInfoError> {
+        self.get_paginated(self.api.forks(owner, repo, sort)).await
+    }
+
+    /// List the milestones of a repository.
+    pub async fn list_milestones(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        options: &ListMilestonesOptions,
+    ) -> Result<Vec<GhMilestone>, GhRepoInfoError> {
+        self.get_paginated(self.api.milestones(owner, repo, options))
+            .await
+    }
+
+    /// List the deployment environments of a repository.
+    ///
+    /// Only a single page is fetched, by default the first
+    /// 100 environments, see [`Client::with_pagination()`].
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub async fn get_environments(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhEnvironment>, GhRepoInfoError> {
+        let environments: GhEnvironments =
+            self.send_json(&self.api.environments(owner, repo)).await?;
+        Ok(environments.environments)
+    }
+
+    /// Get the GitHub Actions workflows of a repository.
+    ///
+    /// All workflows are returned, including workflows that
+    /// have been disabled, see [`GhWorkflow::state`]. If a page is set
+    /// using [`Client::with_pagination()`], only that page is returned.
+    pub async fn get_workflows(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhWorkflow>, GhRepoInfoError> {
+        let (owner, repo) = (owner.as_ref(), repo.as_ref());
+        let mut workflows = Vec::new();
+        let mut page = Some(self.api.first_workflows_page());
+        while let Some(current) = page {
+            let resp: GhWorkflows = self
+                .send_json(&self.api.workflows(owner, repo, current))
+                .await?;
+            let page_len = resp.workflows.len();
+            workflows.extend(resp.workflows);
+            page =
+                self.api
+                    .next_workflows_page(current, page_len, workflows.len(), resp.total_count);
+        }
+        Ok(workflows)
+    }
+
+    /// Get the most recent GitHub Actions workflow run of a repository,
+    /// optionally only for a specific workflow or branch.
+    ///
+    /// Returns `None` if there are no matching runs.
+    pub async fn get_latest_run(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        options: &LatestRunOptions,
+    ) -> Result<Option<GhWorkflowRun>, GhRepoInfoError> {
+        let runs: GhWorkflowRuns = self
+            .send_json(&self.api.latest_run(owner, repo, options))
+            .await?;
+        Ok(runs.workflow_runs.into_iter().next())
+    }
+
+    /// Get the recent events of a repository, most recent first.
+    ///
+    /// GitHub only includes events from the past 90 days,
+    /// and at most 300 events.
+    pub async fn get_events(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<GhEvent>, GhRepoInfoError> {
+        self.get_paginated(self.api.events(owner, repo)).await
+    }
+
+    /// Get the number of stars of each of the given `(owner, repo)`
+    /// pairs, using a single GraphQL query,
+    /// see [`graphql::get_stars_bulk()`](crate::graphql::get_stars_bulk).
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    #[cfg(feature = "graphql")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "graphql")))]
+    pub async fn get_stars_bulk(
+        &self,
+        repos: &[(String, String)],
+    ) -> Result<BTreeMap<String, usize>, GhRepoInfoError> {
+        use super::graphql::{stars_from_data, GraphQlResponse, StarsData};
+
+        let req = self.api.stars_bulk(repos);
+        self.api.check(&req)?;
+        if repos.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let resp: GraphQlResponse<StarsData> = self.send_json(&req).await?;
+        Ok(stars_from_data(resp.into_data()?))
+    }
+
+    /// Returns `true` if the authenticated user has starred the repository.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub async fn is_starred(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<bool, GhRepoInfoError> {
+        // Responds with an empty `204 No Content` if starred,
+        // so the body must not be deserialized
+        let req = self.api.starred(Method::GET, owner, repo);
+        let resp = not_found_as_none(self.send(&req).await)?;
+        Ok(resp.is_some())
+    }
+
+    /// Star a repository for the authenticated user.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub async fn star(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(), GhRepoInfoError> {
+        self.send(&self.api.starred(Method::PUT, owner, repo))
+            .await?;
+        Ok(())
+    }
+
+    /// Unstar a repository for the authenticated user.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned.
+    pub async fn unstar(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<(), GhRepoInfoError> {
+        self.send(&self.api.starred(Method::DELETE, owner, repo))
+            .await?;
+        Ok(())
+    }
+
+    /// Get the full profile of a repository owner, using
+    /// `/orgs/{org}` or `/users/{user}` depending on its kind.
+    pub async fn get_owner_profile(
+        &self,
+        owner: &GhRepoOwnerInfo,
+    ) -> Result<GhOwnerProfile, GhRepoInfoError> {
+        self.get_json(self.api.owner_profile(owner)).await
+    }
+
+    /// Check that the token is valid, returning the user it
+    /// authenticates as, e.g. before starting a long running job.
+    ///
+    /// Requires a token, otherwise [`GhRepoInfoError::MissingToken`]
+    /// is returned. An invalid token results in
+    /// [`GhRepoInfoError::Unauthorized`], or
+    /// [`GhRepoInfoError::TokenExpired`].
+    pub async fn verify_token(&self) -> Result<GhAuthInfo, GhRepoInfoError> {
+        let resp = self.send(&self.api.user()).await?;
+        let scopes = oauth_scopes(resp.headers());
+        let user: GhUserRef = json(resp).await?;
+        Ok(GhAuthInfo {
+            login: user.name,
+            scopes,
+        })
+    }
+
+    /// Create an access token for a GitHub App installation, which
+    /// can then be passed to [`ClientBuilder::token()`].
+    ///
+    /// `jwt` is a JSON Web Token signed with the private key of the
+    /// GitHub App, which is sent instead of the token of the `Client`.
+    pub async fn create_installation_token(
+        &self,
+        jwt: impl AsRef<str>,
+        installation_id: u64,
+    ) -> Result<GhInstallationToken, GhRepoInfoError> {
+        let req = self.api.installation_token(jwt.as_ref(), installation_id);
+        self.send_json(&req).await
+    }
+
+    /// Download the avatar image of a user or organization.
+    ///
+    /// If `size` is given, GitHub scales the image to be `size`
+    /// pixels wide and tall. The token is not sent, as avatars
+    /// are served from a different host.
+    pub async fn fetch_avatar(
+        &self,
+        owner: &GhRepoOwnerInfo,
+        size: Option<u32>,
+    ) -> Result<Vec<u8>, GhRepoInfoError> {
+        let resp = self.send(&self.api.avatar(owner, size)).await?;
+        let bytes = resp.bytes().await.map_err(GhRepoInfoError::from_send)?;
+        Ok(bytes.to_vec())
+    }
+
+    fn request(&self, req: &ApiRequest) -> RequestBuilder {
+        let mut builder = self
+            .http
+            .request(req.method.clone(), &req.url)
+            .query(&req.query);
+        for (name, value) in &req.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(token) = &req.bearer {
+            builder = builder.bearer_auth(token);
+        }
+        #[cfg(feature = "graphql")]
+        if let Some(graphql) = &req.graphql {
+            builder = builder.json(graphql);
+        }
+        match self.api.timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
+    }
+
+    async fn send(&self, req: &ApiRequest) -> Result<Response, GhRepoInfoError> {
+        self.api.check(req)?;
+        if let Some(wait) = self.api.rate_limit() {
+            tokio::time::sleep(wait).await;
+        }
+
+        let req = self
+            .request(req)
+            .build()
+            .map_err(GhRepoInfoError::from_send)?;
+        let url = req.url().to_string();
+
+        let start = Instant::now();
+        let resp = self.http.execute(req).await;
+        match &resp {
+            Ok(resp) => self
+                .api
+                .on_request(url, Some(resp.status()), Some(resp.headers()), start),
+            Err(err) => self.api.on_request(url, err.status(), None, start),
+        }
+
+        let resp = resp.map_err(GhRepoInfoError::from_send)?;
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await.unwrap_or_default();
+            return Err(unauthorized(&headers, &body));
+        }
+        check_status(resp.status(), resp.headers())?;
+        Ok(resp)
+    }
+
+    async fn send_json<T>(&self, req: &ApiRequest) -> Result<T, GhRepoInfoError>
+    where
+        T: DeserializeOwned,
+    {
+        json(self.send(req).await?).await
+    }
+
+    /// Same as [`Client::send_json()`], but using the [`ResponseCache`]
+    /// of the `Client`, if any.
+    async fn get_json<T>(&self, req: ApiRequest) -> Result<T, GhRepoInfoError>
+    where
+        T: DeserializeOwned,
+    {
+        let Some(cache) = &self.api.cache else {
+            return self.send_json(&req).await;
+        };
+
+        let (req, cached) = self.api.revalidate(cache, req);
+        let resp = match revalidated(self.send(&req).await, cached)? {
+            ControlFlow::Continue(resp) => resp,
+            ControlFlow::Break(value) => return Ok(value),
+        };
+        check_content_type(resp.headers())?;
+
+        let request_id = request_id(resp.headers());
+        let etag = etag(resp.headers());
+        let body = resp.bytes().await.map_err(GhRepoInfoError::from_send)?;
+        store(cache, &req.url, etag, request_id, &body)
+    }
+
+    /// Fetches all pages by following the `Link` header.
+    async fn get_paginated<T>(&self, req: ApiRequest) -> Result<Vec<T>, GhRepoInfoError>
+    where
+        T: DeserializeOwned,
+    {
+        self.paginate(req).try_collect().await
+    }
+
+    /// Streams the items of all pages, requesting the next page
+    /// once the items of the current page are exhausted.
+    ///
+    /// After yielding an error, the stream ends.
+    fn paginate<'a, T>(
+        &'a self,
+        req: ApiRequest,
+    ) -> impl Stream<Item = Result<T, GhRepoInfoError>> + 'a
+    where
+        T: DeserializeOwned + 'a,
+    {
+        let state = (Some(req), None::<Page<T>>);
+        stream::try_unfold(state, move |(mut next, mut page)| async move {
+            loop {
+                if let Some(items) = &mut page {
+                    if let Some(item) = items.next().await? {
+                        return Ok(Some((item, (next, page))));
+                    }
+                }
+
+                let Some(req) = next.take() else {
+                    return Ok(None);
+                };
+                let resp = self.send(&req).await?;
+                next = self.api.next_page(&req, resp.headers());
+                page = Some(Page::new(resp, self.api.stream_listings).await?);
+            }
+        })
+    }
+}
+
+/// The items of a single page of a listing.
+enum Page<T> {
+    Buffered(vec::IntoIter<T>),
+    /// See [`ClientBuilder::stream_listings()`].
+    Streamed(Box<JsonItems>),
+}
+
+impl<T> Page<T>
+where
+    T: DeserializeOwned,
+{
+    async fn new(resp: Response, stream: bool) -> Result<Self, GhRepoInfoError> {
+        if stream {
+            return Ok(Self::Streamed(Box::new(JsonItems::new(resp)?)));
+        }
+        let page = json::<Vec<T>>(resp).await?;
+        Ok(Self::Buffered(page.into_iter()))
+    }
+
+    async fn next(&mut self) -> Result<Option<T>, GhRepoInfoError> {
+        match self {
+            Self::Buffered(items) => Ok(items.next()),
+            Self::Streamed(items) => items.next().await,
+        }
+    }
+}
+
+/// Deserializes the elements of a JSON array response one at
+/// a time, as the chunks of the body are received.
+struct JsonItems {
+    resp: Response,
+    parser: JsonArrayParser,
+    items: VecDeque<Vec<u8>>,
+    request_id: Option<String>,
+    done: bool,
+}
+
+impl JsonItems {
+    fn new(resp: Response) -> Result<Self, GhRepoInfoError> {
+        check_content_type(resp.headers())?;
+        let request_id = request_id(resp.headers());
+        Ok(Self {
+            resp,
+            parser: JsonArrayParser::default(),
+            items: VecDeque::new(),
+            request_id,
+            done: false,
+        })
+    }
+
+    async fn next<T>(&mut self) -> Result<Option<T>, GhRepoInfoError>
+    where
+        T: DeserializeOwned,
+    {
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return serde_json::from_slice(&item).map(Some).map_err(|err| {
+                    GhRepoInfoError::DeserializeJsonFailed(err, self.request_id.clone())
+                });
+            }
+            if self.done {
+                return Ok(None);
+            }
+
+            let chunk = self
+                .resp
+                .chunk()
+                .await
+                .map_err(GhRepoInfoError::from_send)?;
+            let res = match chunk {
+                Some(chunk) => self.parser.push(&chunk, &mut self.items),
+                None => {
+                    self.done = true;
+                    self.parser.finish()
+                }
+            };
+            res.map_err(|err| {
+                GhRepoInfoError::DeserializeJsonFailed(err, self.request_id.clone())
+            })?;
+        }
+    }
+}
+
+async fn json<T>(resp: Response) -> Result<T, GhRepoInfoError>
+where
+    T: DeserializeOwned,
+{
+    check_content_type(resp.headers())?;
+    let request_id = request_id(resp.headers());
+    resp.json::<T>()
+        .await
+        .map_err(|err| GhRepoInfoError::from_deserialize(err, request_id))
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientBuilder {
+    pub(crate) token: Option<String>,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) base_url: String,
+    pub(crate) redirect: RedirectPolicy,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) http2_prior_knowledge: bool,
+    pub(crate) http2_adaptive_window: Option<bool>,
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+    pub(crate) api_version: Option<String>,
+    pub(crate) accept: Option<String>,
+    pub(crate) on_request: Option<MetricsHook>,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) cache: Option<CacheHandle>,
+    pub(crate) stream_listings: bool,
+    pub(crate) local_address: Option<IpAddr>,
+    pub(crate) resolve: BTreeMap<String, Vec<SocketAddr>>,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self {
+            token: None,
+            user_agent: Some(env!("CARGO_PKG_NAME").to_owned()),
+            base_url: API_URL.to_owned(),
+            redirect: RedirectPolicy::default(),
+            timeout: None,
+            connect_timeout: None,
+            http2_prior_knowledge: false,
+            http2_adaptive_window: None,
+            http2_keep_alive_interval: None,
+            api_version: None,
+            accept: None,
+            on_request: None,
+            rate_limiter: None,
+            cache: None,
+            stream_listings: false,
+            local_address: None,
+            resolve: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a builder using the token and host of the `gh` CLI,
+    /// see [`get_with_gh_auth()`](crate::get_with_gh_auth).
+    pub fn from_gh_cli() -> Self {
+        let mut builder = Self::new().base_url(gh_api_url());
+        builder.token = gh_cli_token();
+        builder
+    }
+
+    /// Authenticate requests using a personal access token,
+    /// OAuth token, or GitHub App installation token.
+    ///
+    /// Installation tokens can be created using
+    /// [`Client::create_installation_token()`], and expire after
+    /// an hour, see [`GhRepoInfoError::TokenExpired`].
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Default is the crate name, i.e. `gh-repo-info`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Don't send a `User-Agent` header.
+    ///
+    /// GitHub rejects requests without a `User-Agent` with
+    /// `403 Forbidden`, so this is only useful when requests
+    /// pass through a proxy adding one.
+    pub fn no_user_agent(mut self) -> Self {
+        self.user_agent = None;
+        self
+    }
+
+    /// Default is `https://api.github.com`.
+    ///
+    /// For GitHub Enterprise Server use `https://HOSTNAME/api/v3`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Default is [`RedirectPolicy::Limited(10)`](RedirectPolicy::Limited).
+    ///
+    /// GitHub responds with `301 Moved Permanently` for renamed and
+    /// transferred repositories. With [`RedirectPolicy::None`] that
+    /// results in [`GhRepoInfoError::ResponseNonSuccess`] with the
+    /// original status, instead of transparently following it.
+    pub fn redirect(mut self, redirect: RedirectPolicy) -> Self {
+        self.redirect = redirect;
+        self
+    }
+
+    /// Total timeout of each request, from connecting until the
+    /// response body has been read.
+    ///
+    /// Default is no timeout, except for `blocking::Client`
+    /// which defaults to 30 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout of only connecting to the server, independent of
+    /// [`ClientBuilder::timeout()`].
+    ///
+    /// Default is no connect timeout.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Only use HTTP/2, without first negotiating it.
+    ///
+    /// Default is negotiating the HTTP version with the server.
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Whether to use an adaptive flow control window for HTTP/2
+    /// connections, which can improve throughput of large responses.
+    ///
+    /// Default is the same as `reqwest`.
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = Some(enabled);
+        self
+    }
+
+    /// Send HTTP/2 keep-alive pings at the given `interval`,
+    /// keeping idle connections open.
+    ///
+    /// Only supported by the async [`Client`], and ignored by
+    /// `blocking::Client`. Default is not sending pings.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Sent as the `X-GitHub-Api-Version` header, e.g. `"2022-11-28"`.
+    ///
+    /// Default is not sending the header, in which case GitHub
+    /// uses its default API version.
+    pub fn api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Sent as the `Accept` header of API requests, e.g.
+    /// `"application/vnd.github.star+json"` to opt into
+    /// media types of preview features.
+    ///
+    /// Default is not sending the header, in which case GitHub
+    /// uses `application/vnd.github+json`.
+    pub fn accept(mut self, accept: impl Into<String>) -> Self {
+        self.accept = Some(accept.into());
+        self
+    }
+
+    /// Calls `f` after each request, both when it succeeds and fails,
+    /// e.g. for collecting metrics.
+    ///
+    /// `f` is called once the response headers have been received,
+    /// before the status is checked and the body is read.
+    pub fn on_request(mut self, f: impl Fn(&RequestMetrics) + Send + Sync + 'static) -> Self {
+        self.on_request = Some(MetricsHook::new(f));
+        self
+    }
+
+    /// Wait for `rate_limiter` before sending each request,
+    /// including each page of a listing and each retry.
+    ///
+    /// Default is no rate limiting.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Cache responses in `cache`, revalidating them using their `ETag`.
+    ///
+    /// Only requests for a single resource are cached, e.g. using
+    /// [`Client::get()`], not listings.
+    ///
+    /// Default is no caching.
+    pub fn cache(mut self, cache: impl ResponseCache + 'static) -> Self {
+        self.cache = Some(CacheHandle::new(cache));
+        self
+    }
+
+    /// Deserialize the items of listings one at a time, as the
+    /// response of each page is received, instead of first
+    /// receiving and deserializing the whole page.
+    ///
+    /// This reduces peak memory for pages of large items, and lets
+    /// [`Client::org_repos_stream()`] and `blocking::Client::org_repos_iter()`
+    /// yield items before the rest of the page is received.
+    ///
+    /// Default is `false`.
+    pub fn stream_listings(mut self, enabled: bool) -> Self {
+        self.stream_listings = enabled;
+        self
+    }
+
+    /// Bind to the given local IP address when connecting.
+    ///
+    /// Binding to [`Ipv6Addr::UNSPECIFIED`](std::net::Ipv6Addr::UNSPECIFIED)
+    /// forces connecting over IPv6.
+    pub fn local_address(mut self, addr: impl Into<IpAddr>) -> Self {
+        self.local_address = Some(addr.into());
+        self
+    }
+
+    /// Resolve `domain` to the given address instead of using DNS,
+    /// e.g. `"api.github.com"`. Calling it multiple times for the
+    /// same `domain` adds additional addresses.
+    ///
+    /// The port of `addr` is ignored, and the port of the URL is used.
+    pub fn resolve(mut self, domain: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve.entry(domain.into()).or_default().push(addr);
+        self
+    }
+
+    pub fn build(self) -> Result<Client, GhRepoInfoError> {
+        let mut http = reqwest::Client::builder().redirect(self.redirect.to_reqwest());
+        if let Some(user_agent) = &self.user_agent {
+            http = http.user_agent(user_agent.as_str());
+        }
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            http = http.connect_timeout(connect_timeout);
+        }
+        if self.http2_prior_knowledge {
+            http = http.http2_prior_knowledge();
+        }
+        if let Some(enabled) = self.http2_adaptive_window {
+            http = http.http2_adaptive_window(enabled);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            http = http.http2_keep_alive_interval(interval);
+        }
+        if let Some(local_address) = self.local_address {
+            http = http.local_address(local_address);
+        }
+        for (domain, addrs) in &self.resolve {
+            http = http.resolve_to_addrs(domain, addrs);
+        }
+        let http = http.build().map_err(GhRepoInfoError::BuildClient)?;
+        Ok(Client {
+            http,
+            api: Api::new(self),
+        })
+    }
+
+    #[cfg(feature = "blocking")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "blocking")))]
+    pub fn build_blocking(self) -> Result<crate::blocking::Client, GhRepoInfoError> {
+        crate::blocking::Client::from_builder(self)
+    }
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RedirectPolicy {
+    /// Follow up to the given number of redirects.
+    Limited(usize),
+    /// Never follow redirects.
+    None,
+}
+
+impl RedirectPolicy {
+    pub(crate) fn to_reqwest(self) -> reqwest::redirect::Policy {
+        match self {
+            Self::Limited(max) => reqwest::redirect::Policy::limited(max),
+            Self::None => reqwest::redirect::Policy::none(),
+        }
+    }
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self::Limited(10)
+    }
+}
+
+/// A request as it would be sent by a [`Client`],
+/// see [`Client::build_request()`].
+#[derive(Clone, Debug)]
+pub struct PreparedRequest {
+    pub method: Method,
+    pub url: String,
+    /// All headers sent with the request, including the `User-Agent`.
+    ///
+    /// If the `Client` has a token, this includes the `Authorization`
+    /// header, which is marked as sensitive and hidden by `Debug`.
+    pub headers: HeaderMap,
+}
+
+impl PreparedRequest {
+    pub(crate) fn new(
+        method: Method,
+        url: String,
+        mut headers: HeaderMap,
+        user_agent: Option<&HeaderValue>,
+    ) -> Self {
+        if let Some(user_agent) = user_agent {
+            headers.insert(USER_AGENT, user_agent.clone());
+        }
+        Self {
+            method,
+            url,
+            headers,
+        }
+    }
+}
+
+/// Returns `None` if `user_agent` is not a valid header value,
+/// in which case building the client fails regardless.
+pub(crate) fn user_agent_header(user_agent: &str) -> Option<HeaderValue> {
+    HeaderValue::from_str(user_agent).ok()
+}
+
+/// Options for [`get_with()`](crate::get_with), built once and
+/// reused across calls.
+///
+/// Fields left as `None` use the defaults of [`ClientBuilder`].
+#[derive(Clone, Default, Debug)]
+pub struct RequestOptions {
+    /// See [`ClientBuilder::token()`].
+    pub token: Option<String>,
+    /// See [`ClientBuilder::user_agent()`].
+    pub user_agent: Option<String>,
+    /// See [`ClientBuilder::base_url()`].
+    pub base_url: Option<String>,
+    /// See [`ClientBuilder::timeout()`].
+    pub timeout: Option<Duration>,
+    /// See [`ClientBuilder::api_version()`].
+    pub api_version: Option<String>,
+}
+
+impl From<&RequestOptions> for ClientBuilder {
+    fn from(options: &RequestOptions) -> Self {
+        let mut builder = ClientBuilder::new();
+        builder.token = options.token.clone();
+        if let Some(user_agent) = &options.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(base_url) = &options.base_url {
+            builder = builder.base_url(base_url);
+        }
+        builder.timeout = options.timeout;
+        builder.api_version = options.api_version.clone();
+        builder
+    }
+}
+
+/// Options for [`Client::search_by_topic()`].
+#[derive(Clone, Default, Debug)]
+pub struct SearchByTopicOptions {
+    /// Only include repositories with more than the given number of stars.
+    pub min_stars: Option<usize>,
+    /// Only include repositories written in the given language, e.g. `"rust"`.
+    pub language: Option<String>,
+}
+
+impl SearchByTopicOptions {
+    pub(crate) fn query(&self, topic: &str) -> String {
+        let mut query = format!("topic:{topic}");
+        if let Some(min_stars) = self.min_stars {
+            query.push_str(&format!(" stars:>{min_stars}"));
+        }
+        if let Some(language) = &self.language {
+            query.push_str(&format!(" language:{language}"));
+        }
+        query
+    }
+}
+
+/// Options for [`Client::list_my_repos()`].
+#[derive(Clone, Default, Debug)]
+pub struct ListMyReposOptions {
+    /// Default is [`VisibilityFilter::All`].
+    pub visibility: Option<VisibilityFilter>,
+    /// Default is all affiliations.
+    pub affiliation: Vec<Affiliation>,
+    /// Default includes all repositories.
+    pub filter: RepoFilter,
+}
+
+impl ListMyReposOptions {
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(visibility) = self.visibility {
+            query.push(("visibility", visibility.as_str().to_owned()));
+        }
+        if !self.affiliation.is_empty() {
+            let affiliation = self
+                .affiliation
+                .iter()
+                .map(|affiliation| affiliation.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            query.push(("affiliation", affiliation));
+        }
+        query
+    }
+}
+
+/// Repositories to skip when listing repositories,
+/// e.g. [`Client::list_my_repos()`].
+///
+/// GitHub cannot filter by these, so the repositories are
+/// still fetched and then filtered out.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RepoFilter {
+    /// Skip forks, see [`GhRepoInfo::is_fork`].
+    ///
+    /// Default is `false`.
+    pub exclude_forks: bool,
+    /// Skip archived repositories, see [`GhRepoInfo::is_archived`].
+    ///
+    /// Default is `false`.
+    pub exclude_archived: bool,
+}
+
+impl RepoFilter {
+    pub(crate) fn includes(&self, repo: &GhRepoInfo) -> bool {
+        if self.exclude_forks && repo.is_fork {
+            return false;
+        }
+        if self.exclude_archived && repo.is_archived {
+            return false;
+        }
+        true
+    }
+}
+
+/// Options for [`Client::list_org_members()`].
+#[derive(Clone, Default, Debug)]
+pub struct ListOrgMembersOptions {
+    /// Filtering by [`OrgMemberRole::Admin`] or [`OrgMemberRole::Member`]
+    /// requires the authenticated user to be a member of the organization.
+    ///
+    /// Default is [`OrgMemberRole::All`].
+    pub role: Option<OrgMemberRole>,
+}
+
+impl ListOrgMembersOptions {
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(role) = self.role {
+            query.push(("role", role.as_str().to_owned()));
+        }
+        query
+    }
+}
+
+/// Options for [`Client::list_milestones()`].
+#[derive(Clone, Default, Debug)]
+pub struct ListMilestonesOptions {
+    /// Default is [`StateFilter::Open`].
+    pub state: Option<StateFilter>,
+}
+
+impl ListMilestonesOptions {
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(state) = self.state {
+            query.push(("state", state.as_str().to_owned()));
+        }
+        query
+    }
+}
+
+/// Options for [`Client::get_latest_run()`].
+#[derive(Clone, Default, Debug)]
+pub struct LatestRunOptions {
+    /// Only include runs of this workflow, see [`GhWorkflow::id`].
+    pub workflow_id: Option<u64>,
+    /// Only include runs for this branch.
+    pub branch: Option<String>,
+}
+
+impl LatestRunOptions {
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("per_page", "1".to_owned())];
+        if let Some(branch) = &self.branch {
+            query.push(("branch", branch.clone()));
+        }
+        query
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VisibilityFilter {
+    All,
+    Public,
+    Private,
+}
+
+impl VisibilityFilter {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Public => "public",
+            Self::Private => "private",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Affiliation {
+    /// Repositories owned by the authenticated user.
+    Owner,
+    /// Repositories the user has been added to as a collaborator.
+    Collaborator,
+    /// Repositories the user has access to through
+    /// being a member of an organization.
+    OrganizationMember,
+}
+
+impl Affiliation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Collaborator => "collaborator",
+            Self::OrganizationMember => "organization_member",
+        }
+    }
+}
+
+/// Sort order of [`Client::get_forks()`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ForkSort {
+    #[default]
+    Newest,
+    Oldest,
+    /// Most stars first.
+    Stargazers,
+}
+
+impl ForkSort {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Newest => "newest",
+            Self::Oldest => "oldest",
+            Self::Stargazers => "stargazers",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrgMemberRole {
+    All,
+    /// Owners of the organization.
+    Admin,
+    /// Non-owner members of the organization.
+    Member,
+}
+
+impl OrgMemberRole {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Admin => "admin",
+            Self::Member => "member",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StateFilter {
+    Open,
+    Closed,
+    All,
+}
+
+impl StateFilter {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+            Self::All => "all",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    use futures_util::StreamExt;
+
+    use crate::test_util::{repo_ok, serve, serve_sequence, serve_stalled};
+    use crate::tests::repo_json;
+    use crate::MemoryCache;
+
+    fn client(base_url: String) -> Client {
+        Client::builder()
+            .token("ghp_test")
+            .base_url(base_url)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn is_starred_no_content() {
+        let base_url = serve("HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n");
+        let res = client(base_url).is_starred("rust-lang", "rust").await;
+        assert!(matches!(res, Ok(true)), "{res:?}");
+    }
+
+    #[tokio::test]
+    async fn is_starred_not_found() {
+        let base_url = serve(concat!(
+            "HTTP/1.1 404 Not Found\r\n",
+            "Content-Type: application/json\r\n",
+            "Content-Length: 23\r\n",
+            "Connection: close\r\n",
+            "\r\n",
+            r#"{"message":"Not Found"}"#,
+        ));
+        let res = client(base_url).is_starred("rust-lang", "rust").await;
+        assert!(matches!(res, Ok(false)), "{res:?}");
+    }
+
+    #[tokio::test]
+    async fn unauthorized_token_expired() {
+        let base_url = serve(concat!(
+            "HTTP/1.1 401 Unauthorized\r\n",
+            "Content-Type: application/json\r\n",
+            "GitHub-Authentication-Token-Expiration: 2020-01-01 00:00:00 UTC\r\n",
+            "Content-Length: 29\r\n",
+            "Connection: close\r\n",
+            "\r\n",
+            r#"{"message":"Bad credentials"}"#,
+        ));
+        let res = client(base_url).get("rust-lang", "rust").await;
+        assert!(
+            matches!(res, Err(GhRepoInfoError::TokenExpired(_))),
+            "{res:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn unauthorized() {
+        let base_url = serve(concat!(
+            "HTTP/1.1 401 Unauthorized\r\n",
+            "Content-Type: application/json\r\n",
+            "Content-Length: 29\r\n",
+            "Connection: close\r\n",
+            "\r\n",
+            r#"{"message":"Bad credentials"}"#,
+        ));
+        let res = client(base_url).get("rust-lang", "rust").await;
+        assert!(
+            matches!(res, Err(GhRepoInfoError::Unauthorized(_))),
+            "{res:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn unexpected_content_type() {
+        let base_url = serve(concat!(
+            "HTTP/1.1 200 OK\r\n",
+            "Content-Type: text/html; charset=utf-8\r\n",
+            "Content-Length: 15\r\n",
+            "Connection: close\r\n",
+            "\r\n",
+            "<html></html>\r\n",
+        ));
+        let res = client(base_url).get("rust-lang", "rust").await;
+        assert!(
+            matches!(&res, Err(GhRepoInfoError::UnexpectedContentType(content_type)) if content_type == "text/html; charset=utf-8"),
+            "{res:?}"
+        );
+    }
+
+    const NOT_MODIFIED: &str =
+        "HTTP/1.1 304 Not Modified\r\nETag: \"abc\"\r\nConnection: close\r\n\r\n";
+
+    fn if_none_match(request: &str) -> Option<&str> {
+        request.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("if-none-match")
+                .then(|| value.trim())
+        })
+    }
+
+    #[tokio::test]
+    async fn get_map_dedups() {
+        let (base_url, requests) = serve_sequence(vec![repo_ok(), repo_ok()]);
+        let repos = client(base_url)
+            .get_map(["rust-lang/rust", "rust-lang/rust"])
+            .await;
+        assert_eq!(repos.len(), 1);
+        assert_eq!(
+            repos["rust-lang/rust"].as_ref().unwrap().full_name,
+            "rust-lang/rust"
+        );
+        assert_eq!(requests.try_iter().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn cache_revalidates_with_etag() {
+        let (base_url, requests) = serve_sequence(vec![repo_ok(), NOT_MODIFIED.to_owned()]);
+        let client = Client::builder()
+            .token("ghp_test")
+            .base_url(base_url)
+            .cache(MemoryCache::new())
+            .build()
+            .unwrap();
+
+        let repo = client.get("rust-lang", "rust").await.unwrap();
+        assert_eq!(repo.full_name, "rust-lang/rust");
+        assert_eq!(if_none_match(&requests.recv().unwrap()), None);
+
+        let repo = client.get("rust-lang", "rust").await.unwrap();
+        assert_eq!(repo.full_name, "rust-lang/rust");
+        assert_eq!(if_none_match(&requests.recv().unwrap()), Some("\"abc\""));
+    }
+
+    #[tokio::test]
+    async fn refresh_skips_cache() {
+        let (base_url, requests) =
+            serve_sequence(vec![repo_ok(), repo_ok(), NOT_MODIFIED.to_owned()]);
+        let client = Client::builder()
+            .token("ghp_test")
+            .base_url(base_url)
+            .cache(MemoryCache::new())
+            .build()
+            .unwrap();
+
+        client.get("rust-lang", "rust").await.unwrap();
+        requests.recv().unwrap();
+
+        // Only the `ETag` of `cached` is used, even though
+        // the response cache of the client holds the repository
+        let mut cached = None;
+        let changed = client.refresh("rust-lang", "rust", &mut cached).await;
+        assert!(changed.unwrap());
+        assert_eq!(if_none_match(&requests.recv().unwrap()), None);
+        let cached_repo = cached.as_ref().unwrap();
+        assert_eq!(cached_repo.info.full_name, "rust-lang/rust");
+        assert_eq!(cached_repo.etag.as_deref(), Some("\"abc\""));
+
+        let changed = client.refresh("rust-lang", "rust", &mut cached).await;
+        assert!(!changed.unwrap());
+        assert_eq!(if_none_match(&requests.recv().unwrap()), Some("\"abc\""));
+        assert!(cached.is_some());
+    }
+
+    #[tokio::test]
+    async fn drop_aborts_request() {
+        let base_url = serve_stalled("");
+        let client = client(base_url);
+
+        let start = Instant::now();
+        let fut = client.get_with_default_retries("rust-lang", "rust");
+        let res = tokio::time::timeout(Duration::from_millis(200), fut).await;
+        assert!(res.is_err(), "{res:?}");
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // The connection of the aborted request is closed as well,
+        // so nothing outlives the dropped future
+        tokio::task::yield_now().await;
+        let metrics = tokio::runtime::Handle::current().metrics();
+        assert_eq!(metrics.num_alive_tasks(), 0);
+    }
+
+    #[tokio::test]
+    async fn stream_listings_yields_before_end_of_page() {
+        let base_url = serve_stalled(format!(
+            concat!(
+                "HTTP/1.1 200 OK\r\n",
+                "Content-Type: application/json\r\n",
+                "Content-Length: 100000\r\n",
+                "\r\n",
+                "[{},",
+            ),
+            repo_json(),
+        ));
+        let client = Client::builder()
+            .base_url(base_url)
+            .stream_listings(true)
+            .build()
+            .unwrap();
+
+        let repos = client.org_repos_stream("rust-lang", &RepoFilter::default());
+        let mut repos = std::pin::pin!(repos);
+        let repo = tokio::time::timeout(Duration::from_secs(5), repos.next())
+            .await
+            .expect("first item was not yielded before the end of the page");
+        assert_eq!(repo.unwrap().unwrap().full_name, "rust-lang/rust");
+    }
+}
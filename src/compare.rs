@@ -0,0 +1,44 @@
+use std::cmp::Ordering;
+
+use super::{time, GhRepoInfo};
+
+/// Differences between two repositories, see [`GhRepoInfo::compare()`].
+///
+/// Differences are computed as `self - other`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GhRepoComparison {
+    pub stars_diff: i64,
+    pub forks_diff: i64,
+    pub open_issues_diff: i64,
+    /// Compares `pushed_at`, i.e. [`Ordering::Greater`] if `self` was
+    /// pushed to more recently than `other`.
+    ///
+    /// `None` if either has not been pushed to.
+    pub pushed_at: Option<Ordering>,
+    /// `true` if both have the same primary language,
+    /// including if neither has one.
+    pub same_language: bool,
+}
+
+impl GhRepoInfo {
+    /// Compares `self` to `other`, e.g. for presenting them side by side.
+    pub fn compare(&self, other: &GhRepoInfo) -> GhRepoComparison {
+        let pushed_at =
+            |repo: &GhRepoInfo| repo.pushed_at.as_deref().and_then(time::parse_timestamp);
+        let pushed_at = match (pushed_at(self), pushed_at(other)) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => None,
+        };
+        GhRepoComparison {
+            stars_diff: diff(self.stargazers_count, other.stargazers_count),
+            forks_diff: diff(self.forks_count, other.forks_count),
+            open_issues_diff: diff(self.open_issues_count, other.open_issues_count),
+            pushed_at,
+            same_language: self.language == other.language,
+        }
+    }
+}
+
+fn diff(a: usize, b: usize) -> i64 {
+    (a as i64).saturating_sub(b as i64)
+}
@@ -0,0 +1,53 @@
+use serde::Deserialize;
+
+/// A deployment environment.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_environments()`](crate::Client::get_environments)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhEnvironment {
+    pub name: String,
+    #[serde(rename = "html_url")]
+    pub url: String,
+    #[serde(default)]
+    pub protection_rules: Vec<GhProtectionRule>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhProtectionRule {
+    #[serde(rename = "type")]
+    pub kind: GhProtectionRuleKind,
+    /// Minutes to wait before deploying,
+    /// only present for [`GhProtectionRuleKind::WaitTimer`].
+    #[serde(default)]
+    pub wait_timer: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(from = "String")]
+pub enum GhProtectionRuleKind {
+    RequiredReviewers,
+    WaitTimer,
+    BranchPolicy,
+    /// Any other rule, e.g. a custom deployment protection rule.
+    Other(String),
+}
+
+impl From<String> for GhProtectionRuleKind {
+    fn from(kind: String) -> Self {
+        match kind.as_str() {
+            "required_reviewers" => Self::RequiredReviewers,
+            "wait_timer" => Self::WaitTimer,
+            "branch_policy" => Self::BranchPolicy,
+            _ => Self::Other(kind),
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+#[derive(Deserialize, Debug)]
+pub(crate) struct GhEnvironments {
+    pub(crate) environments: Vec<GhEnvironment>,
+}
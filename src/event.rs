@@ -0,0 +1,65 @@
+use serde::Deserialize;
+
+/// An event of a repository's activity feed.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_events()`](crate::Client::get_events)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: GhEventKind,
+    pub actor: GhEventActor,
+    /// ISO 8601 timestamp, e.g. `"2022-06-09T12:47:28Z"`.
+    pub created_at: String,
+    /// Event specific data, which varies by [`kind`](Self::kind).
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhEventActor {
+    #[serde(rename = "login")]
+    pub name: String,
+    pub avatar_url: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(from = "String")]
+pub enum GhEventKind {
+    Push,
+    PullRequest,
+    PullRequestReview,
+    PullRequestReviewComment,
+    Issues,
+    IssueComment,
+    /// Starring a repository.
+    Watch,
+    Fork,
+    Create,
+    Delete,
+    Release,
+    /// Any other event type, e.g. `"GollumEvent"`.
+    Other(String),
+}
+
+impl From<String> for GhEventKind {
+    fn from(kind: String) -> Self {
+        match kind.as_str() {
+            "PushEvent" => Self::Push,
+            "PullRequestEvent" => Self::PullRequest,
+            "PullRequestReviewEvent" => Self::PullRequestReview,
+            "PullRequestReviewCommentEvent" => Self::PullRequestReviewComment,
+            "IssuesEvent" => Self::Issues,
+            "IssueCommentEvent" => Self::IssueComment,
+            "WatchEvent" => Self::Watch,
+            "ForkEvent" => Self::Fork,
+            "CreateEvent" => Self::Create,
+            "DeleteEvent" => Self::Delete,
+            "ReleaseEvent" => Self::Release,
+            _ => Self::Other(kind),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_HOST: &str = "github.com";
+
+/// Returns the OAuth token stored by the [`gh`](https://cli.github.com) CLI
+/// in its `hosts.yml` file, for the host given by the `GH_HOST` environment
+/// variable, or `github.com` if unset.
+///
+/// The config directory is resolved like `gh` does, i.e. `$GH_CONFIG_DIR`,
+/// `$XDG_CONFIG_HOME/gh`, `%AppData%/GitHub CLI` on Windows, and otherwise
+/// `~/.config/gh`.
+///
+/// Returns `None` if the file or the entry is missing. Newer versions of `gh`
+/// store the token in the system keyring instead, in which case it is not
+/// found either.
+pub fn gh_cli_token() -> Option<String> {
+    hosts_token(&gh_config_dir()?, &gh_host())
+}
+
+/// Returns the token of `host` in the `hosts.yml` file in `config_dir`.
+fn hosts_token(config_dir: &Path, host: &str) -> Option<String> {
+    let hosts = fs::read_to_string(config_dir.join("hosts.yml")).ok()?;
+    parse_hosts_token(&hosts, host)
+}
+
+/// Returns the API base URL of the host given by `GH_HOST`.
+pub(crate) fn gh_api_url() -> String {
+    let host = gh_host();
+    if host == DEFAULT_HOST {
+        crate::API_URL.to_owned()
+    } else {
+        format!("https://{host}/api/v3")
+    }
+}
+
+fn gh_host() -> String {
+    env::var("GH_HOST")
+        .ok()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| DEFAULT_HOST.to_owned())
+}
+
+fn gh_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("GH_CONFIG_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(dir).join("gh"));
+    }
+    if cfg!(windows) {
+        if let Some(dir) = env::var_os("AppData") {
+            return Some(PathBuf::from(dir).join("GitHub CLI"));
+        }
+    }
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(".config").join("gh"))
+}
+
+/// Finds `oauth_token` directly under the `host` entry, e.g.:
+///
+/// ```yaml
+/// github.com:
+///     user: octocat
+///     oauth_token: gho_...
+/// ```
+fn parse_hosts_token(hosts: &str, host: &str) -> Option<String> {
+    let mut lines = hosts.lines().skip_while(|line| {
+        let key = line.trim_end().strip_suffix(':');
+        key.map(|key| unquote(key.trim())) != Some(host)
+    });
+    lines.next()?;
+
+    let mut indent = None;
+    for line in lines {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let line_indent = line.len() - trimmed.len();
+        if line_indent == 0 {
+            break;
+        }
+        // Only consider keys directly under the host, not nested ones
+        if *indent.get_or_insert(line_indent) != line_indent {
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        if unquote(key.trim()) == "oauth_token" {
+            let token = unquote(value.trim());
+            return (!token.is_empty()).then(|| token.to_owned());
+        }
+    }
+
+    None
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_token() {
+        let hosts = "\
+github.com:
+    user: octocat
+    oauth_token: gho_plain
+    git_protocol: https
+";
+        let token = parse_hosts_token(hosts, "github.com");
+        assert_eq!(token.as_deref(), Some("gho_plain"));
+    }
+
+    #[test]
+    fn quoted_token() {
+        let hosts = "\
+\"github.com\":
+    \"user\": octocat
+    \"oauth_token\": \"gho_double\"
+'ghe.example.com':
+    'oauth_token': 'gho_single'
+";
+        let token = parse_hosts_token(hosts, "github.com");
+        assert_eq!(token.as_deref(), Some("gho_double"));
+        let token = parse_hosts_token(hosts, "ghe.example.com");
+        assert_eq!(token.as_deref(), Some("gho_single"));
+    }
+
+    #[test]
+    fn nested_users_token() {
+        let hosts = "\
+github.com:
+    users:
+        octocat:
+            oauth_token: gho_nested
+    git_protocol: https
+    user: octocat
+";
+        assert_eq!(parse_hosts_token(hosts, "github.com"), None);
+
+        let hosts = "\
+github.com:
+    users:
+        octocat:
+            oauth_token: gho_nested
+    oauth_token: gho_host
+";
+        let token = parse_hosts_token(hosts, "github.com");
+        assert_eq!(token.as_deref(), Some("gho_host"));
+    }
+
+    #[test]
+    fn missing_host() {
+        let hosts = "\
+ghe.example.com:
+    oauth_token: gho_other
+github.com.example.com:
+    oauth_token: gho_other
+";
+        assert_eq!(parse_hosts_token(hosts, "github.com"), None);
+        assert_eq!(parse_hosts_token("", "github.com"), None);
+    }
+
+    #[test]
+    fn token_of_next_host() {
+        let hosts = "\
+github.com:
+    user: octocat
+ghe.example.com:
+    oauth_token: gho_other
+";
+        assert_eq!(parse_hosts_token(hosts, "github.com"), None);
+    }
+
+    #[test]
+    fn hosts_file() {
+        let config_dir = env::temp_dir().join(format!("gh-repo-info-{}", std::process::id()));
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("hosts.yml"),
+            "github.com:\n    oauth_token: gho_file\n",
+        )
+        .unwrap();
+
+        let token = hosts_token(&config_dir, "github.com");
+        fs::remove_dir_all(&config_dir).unwrap();
+        assert_eq!(token.as_deref(), Some("gho_file"));
+    }
+
+    /// Results in [`ClientBuilder::from_gh_cli()`](crate::ClientBuilder::from_gh_cli)
+    /// being unauthenticated.
+    #[test]
+    fn missing_file() {
+        let config_dir = env::temp_dir().join("gh-repo-info-missing-config");
+        assert_eq!(hosts_token(&config_dir, "github.com"), None);
+    }
+}
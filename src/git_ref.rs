@@ -0,0 +1,26 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhRef {
+    /// Fully qualified name, e.g. `"refs/heads/main"`.
+    #[serde(rename = "ref")]
+    pub name: String,
+    pub object: GhRefObject,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhRefObject {
+    pub sha: String,
+    #[serde(rename = "type")]
+    pub kind: GhObjectKind,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum GhObjectKind {
+    Commit,
+    /// An annotated tag.
+    Tag,
+    Tree,
+    Blob,
+}
@@ -0,0 +1,114 @@
+//! Narrow bulk queries using the GitHub GraphQL API.
+//!
+//! GraphQL requires authentication, so all functions take a token.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Client, GhRepoInfoError};
+
+/// Get the number of stars of each of the given `(owner, repo)` pairs,
+/// using a single GraphQL query.
+///
+/// The returned map is keyed by the full name of each repository,
+/// e.g. `"rust-lang/rust"`, as cased by GitHub. Repositories that
+/// do not exist or are inaccessible are omitted.
+///
+/// This is a shorthand for building a [`Client`] using `token`
+/// followed by [`Client::get_stars_bulk()`].
+pub async fn get_stars_bulk(
+    repos: &[(String, String)],
+    token: impl Into<String>,
+) -> Result<BTreeMap<String, usize>, GhRepoInfoError> {
+    Client::builder()
+        .token(token)
+        .build()?
+        .get_stars_bulk(repos)
+        .await
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct GraphQlRequest {
+    query: String,
+    variables: BTreeMap<String, String>,
+}
+
+impl GraphQlRequest {
+    /// Query aliasing each repository as `r0`, `r1`, etc., passing
+    /// the owner and name as variables to avoid escaping them.
+    pub(crate) fn stars_bulk(repos: &[(String, String)]) -> Self {
+        let mut params = Vec::with_capacity(repos.len());
+        let mut fields = String::new();
+        let mut variables = BTreeMap::new();
+        for (i, (owner, repo)) in repos.iter().enumerate() {
+            params.push(format!("$o{i}: String!, $r{i}: String!"));
+            fields.push_str(&format!(
+                "r{i}: repository(owner: $o{i}, name: $r{i}) {{ nameWithOwner stargazerCount }} "
+            ));
+            variables.insert(format!("o{i}"), owner.clone());
+            variables.insert(format!("r{i}"), repo.clone());
+        }
+        Self {
+            query: format!("query({}) {{ {fields}}}", params.join(", ")),
+            variables,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+impl<T> GraphQlResponse<T> {
+    /// Returns `data`, or fails with the `errors` if there is no data.
+    ///
+    /// Partial errors are ignored when data is present, e.g. a
+    /// missing repository results in a `null` field and an error.
+    pub(crate) fn into_data(self) -> Result<T, GhRepoInfoError> {
+        match self.data {
+            Some(data) => Ok(data),
+            None => Err(GhRepoInfoError::GraphQl(
+                self.errors.into_iter().map(|err| err.message).collect(),
+            )),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct StarsRepo {
+    #[serde(rename = "nameWithOwner")]
+    pub(crate) full_name: String,
+    #[serde(rename = "stargazerCount")]
+    pub(crate) stars: usize,
+}
+
+/// Data of the query built by [`GraphQlRequest::stars_bulk()`], where
+/// inaccessible repositories are `null`.
+pub(crate) type StarsData = BTreeMap<String, Option<StarsRepo>>;
+
+/// Returns the number of stars keyed by full name, omitting
+/// inaccessible repositories.
+pub(crate) fn stars_from_data(data: StarsData) -> BTreeMap<String, usize> {
+    data.into_values()
+        .flatten()
+        .map(|repo| (repo.full_name, repo.stars))
+        .collect()
+}
+
+/// Returns the GraphQL endpoint corresponding to the REST `base_url`,
+/// i.e. `https://HOSTNAME/api/graphql` for GitHub Enterprise Server.
+pub(crate) fn graphql_url(base_url: &str) -> String {
+    match base_url.strip_suffix("/v3") {
+        Some(api_url) => format!("{api_url}/graphql"),
+        None => format!("{base_url}/graphql"),
+    }
+}
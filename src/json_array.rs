@@ -0,0 +1,183 @@
+use std::collections::VecDeque;
+use std::mem;
+
+use serde::de::Error as _;
+
+/// Incrementally splits a JSON array into the raw bytes of its
+/// elements, as the bytes of the array are received.
+///
+/// Only the structure needed to find the end of each element is
+/// checked, the elements themselves are checked when deserialized.
+#[derive(Default, Debug)]
+pub(crate) struct JsonArrayParser {
+    state: State,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    item: Vec<u8>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+enum State {
+    /// Before the opening `[`.
+    #[default]
+    Start,
+    /// Between elements, i.e. after `[` or `,`.
+    Between,
+    Item,
+    /// After the closing `]`.
+    End,
+}
+
+impl JsonArrayParser {
+    /// Parses the next `bytes` of the array, pushing each
+    /// completed element onto `items`.
+    pub(crate) fn push(
+        &mut self,
+        bytes: &[u8],
+        items: &mut VecDeque<Vec<u8>>,
+    ) -> Result<(), serde_json::Error> {
+        for &b in bytes {
+            match self.state {
+                State::Start => match b {
+                    b'[' => self.state = State::Between,
+                    _ if b.is_ascii_whitespace() => {}
+                    _ => return Err(serde_json::Error::custom("expected `[`")),
+                },
+                State::Between => match b {
+                    b']' => self.state = State::End,
+                    b',' => {}
+                    _ if b.is_ascii_whitespace() => {}
+                    _ => {
+                        self.state = State::Item;
+                        self.push_item_byte(b, items);
+                    }
+                },
+                State::Item => self.push_item_byte(b, items),
+                State::End => {
+                    if !b.is_ascii_whitespace() {
+                        return Err(serde_json::Error::custom("trailing characters"));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if the closing `]` has not been parsed.
+    pub(crate) fn finish(&self) -> Result<(), serde_json::Error> {
+        match self.state {
+            State::End => Ok(()),
+            _ => Err(serde_json::Error::custom("EOF while parsing a list")),
+        }
+    }
+
+    fn push_item_byte(&mut self, b: u8, items: &mut VecDeque<Vec<u8>>) {
+        if self.in_string {
+            self.item.push(b);
+            if self.escaped {
+                self.escaped = false;
+            } else if b == b'\\' {
+                self.escaped = true;
+            } else if b == b'"' {
+                self.in_string = false;
+                if self.depth == 0 {
+                    self.complete_item(items, State::Between);
+                }
+            }
+            return;
+        }
+
+        match b {
+            b'"' => {
+                self.item.push(b);
+                self.in_string = true;
+            }
+            b'{' | b'[' => {
+                self.item.push(b);
+                self.depth += 1;
+            }
+            // End of the array following a scalar element
+            b']' if self.depth == 0 => self.complete_item(items, State::End),
+            b'}' | b']' => {
+                self.item.push(b);
+                self.depth = self.depth.saturating_sub(1);
+                if self.depth == 0 {
+                    self.complete_item(items, State::Between);
+                }
+            }
+            // End of a scalar element
+            b',' if self.depth == 0 => self.complete_item(items, State::Between),
+            _ if b.is_ascii_whitespace() && (self.depth == 0) => {
+                self.complete_item(items, State::Between);
+            }
+            _ => self.item.push(b),
+        }
+    }
+
+    fn complete_item(&mut self, items: &mut VecDeque<Vec<u8>>, state: State) {
+        items.push_back(mem::take(&mut self.item));
+        self.state = state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(chunks: &[&str]) -> Result<Vec<String>, serde_json::Error> {
+        let mut parser = JsonArrayParser::default();
+        let mut items = VecDeque::new();
+        for chunk in chunks {
+            parser.push(chunk.as_bytes(), &mut items)?;
+        }
+        parser.finish()?;
+        Ok(items
+            .into_iter()
+            .map(|item| String::from_utf8(item).unwrap())
+            .collect())
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(parse(&[" [ ] "]).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn elements() {
+        let items = parse(&[r#"[{"a":[1,"]"]}, "x\"}", 12, true,null]"#]).unwrap();
+        assert_eq!(
+            items,
+            [r#"{"a":[1,"]"]}"#, r#""x\"}""#, "12", "true", "null"]
+        );
+    }
+
+    #[test]
+    fn split_chunks() {
+        let json = r#"[{"name":"a\\"},{"name":"b"}]"#;
+        let chunks = json
+            .as_bytes()
+            .chunks(1)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parse(&chunks).unwrap(),
+            [r#"{"name":"a\\"}"#, r#"{"name":"b"}"#]
+        );
+    }
+
+    #[test]
+    fn yields_before_end() {
+        let mut parser = JsonArrayParser::default();
+        let mut items = VecDeque::new();
+        parser.push(br#"[{"name":"a"},{"na"#, &mut items).unwrap();
+        assert_eq!(items, [br#"{"name":"a"}"#.to_vec()]);
+        assert!(parser.finish().is_err());
+    }
+
+    #[test]
+    fn not_array() {
+        assert!(parse(&[r#"{"message":"Not Found"}"#]).is_err());
+        assert!(parse(&["[1] 2"]).is_err());
+    }
+}
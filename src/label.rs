@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhLabel {
+    pub name: String,
+    /// Hex color without a leading `#`, e.g. `"d73a4a"`.
+    pub color: String,
+    pub description: Option<String>,
+    #[serde(rename = "default")]
+    pub is_default: bool,
+}
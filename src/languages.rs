@@ -0,0 +1,80 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// Converts the bytes of code written in each language into percentages.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_languages()`](crate::Client::get_languages)."
+)]
+///
+/// The result is sorted by percentage in descending order,
+/// and sums to `100.0` (within floating point precision).
+/// Returns an empty `Vec` if `languages` is empty or all
+/// languages have zero bytes.
+pub fn language_percentages(languages: &BTreeMap<String, u64>) -> Vec<(String, f64)> {
+    let total: u64 = languages.values().sum();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let mut percentages = languages
+        .iter()
+        .map(|(lang, &bytes)| (lang.clone(), bytes as f64 / total as f64 * 100.0))
+        .collect::<Vec<_>>();
+    percentages.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    percentages
+}
+
+/// Returns the hex color GitHub uses for `lang`, e.g. `"#dea584"` for `"Rust"`,
+/// as defined by [linguist](https://github.com/github-linguist/linguist).
+///
+/// Only a curated subset of common languages is covered, see
+/// [`LANGUAGE_COLORS`]. Returns `None` for other languages.
+/// The comparison is case-insensitive.
+pub fn language_color(lang: &str) -> Option<&'static str> {
+    LANGUAGE_COLORS
+        .iter()
+        .find(|(name, _color)| name.eq_ignore_ascii_case(lang))
+        .map(|&(_name, color)| color)
+}
+
+/// Languages and colors covered by [`language_color()`].
+pub const LANGUAGE_COLORS: &[(&str, &str)] = &[
+    ("Assembly", "#6E4C13"),
+    ("C", "#555555"),
+    ("C#", "#178600"),
+    ("C++", "#f34b7d"),
+    ("Clojure", "#db5855"),
+    ("CSS", "#563d7c"),
+    ("Dart", "#00B4AB"),
+    ("Dockerfile", "#384d54"),
+    ("Elixir", "#6e4a7e"),
+    ("Erlang", "#B83998"),
+    ("Go", "#00ADD8"),
+    ("Haskell", "#5e5086"),
+    ("HTML", "#e34c26"),
+    ("Java", "#b07219"),
+    ("JavaScript", "#f1e05a"),
+    ("Julia", "#a270ba"),
+    ("Jupyter Notebook", "#DA5B0B"),
+    ("Kotlin", "#A97BFF"),
+    ("Lua", "#000080"),
+    ("Makefile", "#427819"),
+    ("Objective-C", "#438eff"),
+    ("Perl", "#0298c3"),
+    ("PHP", "#4F5D95"),
+    ("PowerShell", "#012456"),
+    ("Python", "#3572A5"),
+    ("R", "#198CE7"),
+    ("Ruby", "#701516"),
+    ("Rust", "#dea584"),
+    ("Scala", "#c22d40"),
+    ("SCSS", "#c6538c"),
+    ("Shell", "#89e051"),
+    ("Svelte", "#ff3e00"),
+    ("Swift", "#F05138"),
+    ("TypeScript", "#3178c6"),
+    ("Vue", "#41b883"),
+    ("Zig", "#ec915c"),
+];
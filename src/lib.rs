@@ -9,11 +9,14 @@
 //! ```
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "client")]
 //! #[tokio::main]
 //! async fn main() {
 //!     let repo = gh_repo_info::get("rust-lang", "rust").await.unwrap();
 //!     println!("{:#?}", repo);
 //! }
+//! # #[cfg(not(feature = "client"))]
+//! # fn main() {}
 //! ```
 //!
 //! ## Blocking
@@ -24,12 +27,78 @@
 //! ```
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "blocking")]
 //! fn main() {
 //!     let repo = gh_repo_info::blocking::get("rust-lang", "rust").unwrap();
 //!     println!("{:#?}", repo);
 //! }
+//! # #[cfg(not(feature = "blocking"))]
+//! # fn main() {}
 //! ```
 //!
+//! ## Without Networking
+//!
+//! To only use the types, e.g. for deserializing payloads received
+//! from webhooks, disable the default `client` feature. This removes
+//! `Client`, the `get*()` functions, and their dependencies.
+//!
+//! ```toml
+//! [dependencies]
+//! gh-repo-info = { version = "0.1", default-features = false }
+//! ```
+//!
+#![cfg_attr(
+    feature = "client",
+    doc = r#"
+## Authentication
+
+Endpoints that act on behalf of a user, such as [`Client::list_my_repos()`],
+require a [`Client`] configured with a token.
+
+```rust,no_run
+# async fn run() -> Result<(), gh_repo_info::GhRepoInfoError> {
+let client = gh_repo_info::Client::builder()
+    .token("ghp_...")
+    .build()?;
+let repos = client
+    .list_my_repos(&gh_repo_info::ListMyReposOptions::default())
+    .await?;
+# Ok(())
+# }
+```
+
+## Cancellation
+
+Dropping a future returned by [`Client`] cancels the request,
+including any remaining pages of a listing. No work is spawned
+in the background, so nothing outlives the dropped future.
+This includes the delay between attempts in
+[`Client::get_with_retries()`].
+
+```rust,no_run
+# async fn run() -> Result<(), gh_repo_info::GhRepoInfoError> {
+use std::time::Duration;
+
+let client = gh_repo_info::Client::new();
+let fut = client.get_with_default_retries("rust-lang", "rust");
+match tokio::time::timeout(Duration::from_secs(5), fut).await {
+    Ok(repo) => println!("{:#?}", repo?),
+    // The request was dropped and aborted
+    Err(_) => println!("timed out"),
+}
+# Ok(())
+# }
+```
+
+Blocking calls cannot be dropped midway. Instead use
+[`Client::with_cancellation()`] and a [`CancellationToken`],
+which is checked before every request is sent, and interrupts
+the delay between retries. When cancelled,
+[`GhRepoInfoError::Cancelled`] is returned. A request that is
+already in flight runs to completion.
+
+"#
+)]
 //! ## Output
 //!
 //! ```text
@@ -37,6 +106,8 @@
 //!     name: "rust",
 //!     full_name: "rust-lang/rust",
 //!     url: "https://github.com/rust-lang/rust",
+//!     clone_url: "https://github.com/rust-lang/rust.git",
+//!     ssh_url: "git@github.com:rust-lang/rust.git",
 //!     owner: GhRepoOwnerInfo {
 //!         name: "rust-lang",
 //!         url: "https://github.com/rust-lang",
@@ -46,34 +117,188 @@
 //!     stargazers_count: 82127,
 //!     subscribers_count: 1489,
 //!     forks_count: 10830,
+//!     forks: 10830,
+//!     network_count: 10830,
 //!     open_issues_count: 9549,
 //!     is_fork: false,
 //!     is_archived: false,
+//!     is_private: false,
+//!     visibility: Some(
+//!         Public,
+//!     ),
+//!     permissions: None,
 //!     default_branch: "master",
+//!     has_issues: true,
+//!     has_projects: true,
+//!     has_wiki: true,
+//!     has_pages: false,
+//!     has_downloads: true,
+//!     has_discussions: false,
 //!     homepage: "https://www.rust-lang.org",
-//!     description: "Empowering everyone to build reliable and efficient software.",
-//!     license: GhRepoLicenseInfo {
-//!         key: "other",
-//!         name: "Other",
-//!     },
-//!     language: "Rust",
+//!     description: Some(
+//!         "Empowering everyone to build reliable and efficient software.",
+//!     ),
+//!     license: Some(
+//!         GhRepoLicenseInfo {
+//!             key: "other",
+//!             name: "Other",
+//!             spdx_id: Some(
+//!                 "NOASSERTION",
+//!             ),
+//!             url: None,
+//!             node_id: Some(
+//!                 "MDc6TGljZW5zZTA=",
+//!             ),
+//!         },
+//!     ),
+//!     language: Some(
+//!         "Rust",
+//!     ),
 //!     topics: [
 //!         "compiler",
 //!         "hacktoberfest",
 //!         "language",
 //!         "rust",
 //!     ],
+//!     created_at: "2010-06-16T20:39:03Z",
+//!     updated_at: "2022-09-20T19:28:05Z",
+//!     pushed_at: Some(
+//!         "2022-09-20T19:17:35Z",
+//!     ),
 //! }
 //! ```
 
 #![forbid(unsafe_code)]
 #![forbid(elided_lifetimes_in_paths)]
+#![allow(clippy::needless_doctest_main)]
+
+mod advisory;
+#[cfg(feature = "client")]
+mod api;
+mod app;
+mod auth;
+#[cfg(feature = "blocking")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "blocking")))]
+pub mod blocking;
+mod branch;
+mod cache;
+#[cfg(feature = "client")]
+mod cancel;
+#[cfg(feature = "client")]
+mod client;
+mod compare;
+mod environment;
+mod event;
+#[cfg(feature = "client")]
+mod gh_auth;
+mod git_ref;
+#[cfg(feature = "graphql")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "graphql")))]
+pub mod graphql;
+#[cfg(feature = "client")]
+mod json_array;
+mod label;
+mod languages;
+#[cfg(feature = "client")]
+mod metrics;
+mod milestone;
+mod owner;
+mod pages;
+#[cfg(feature = "client")]
+mod pagination;
+pub mod prelude;
+#[cfg(feature = "client")]
+mod provider;
+#[cfg(feature = "client")]
+mod rate_limit;
+mod repo_ref;
+#[cfg(feature = "client")]
+mod response_cache;
+mod score;
+mod search;
+mod stargazer;
+mod summary;
+#[cfg(all(test, feature = "client"))]
+mod test_util;
+mod time;
+mod traffic;
+mod workflow;
 
+pub use advisory::{GhAdvisory, GhAdvisorySeverity};
+pub use app::GhInstallationToken;
+pub use auth::GhAuthInfo;
+pub use branch::{GhBranch, GhCommitRef};
+pub use cache::CachedRepo;
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use cancel::CancellationToken;
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use client::{
+    Affiliation, Client, ClientBuilder, ForkSort, LatestRunOptions, ListMilestonesOptions,
+    ListMyReposOptions, ListOrgMembersOptions, OrgMemberRole, PreparedRequest, RedirectPolicy,
+    RepoFilter, RequestOptions, SearchByTopicOptions, StateFilter, VisibilityFilter,
+};
+pub use compare::GhRepoComparison;
+pub use environment::{GhEnvironment, GhProtectionRule, GhProtectionRuleKind};
+pub use event::{GhEvent, GhEventActor, GhEventKind};
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use gh_auth::gh_cli_token;
+pub use git_ref::{GhObjectKind, GhRef, GhRefObject};
+pub use label::GhLabel;
+pub use languages::{language_color, language_percentages, LANGUAGE_COLORS};
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use metrics::RequestMetrics;
+pub use milestone::{GhMilestone, GhMilestoneState};
+pub use owner::{GhOwnerPlan, GhOwnerProfile};
+pub use pages::{GhPagesInfo, GhPagesStatus};
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use pagination::{InvalidPerPage, Pagination};
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use provider::RepoInfoProvider;
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use rate_limit::RateLimiter;
+pub use repo_ref::{IntoRepoRef, ParseRepoIdError, RepoId};
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub use response_cache::{CachedEntry, MemoryCache, ResponseCache};
+pub use score::ScoreWeights;
+pub use search::SearchResults;
+pub use stargazer::GhStargazer;
+pub use summary::{GhRepoSummary, GhRepoSummaryCamelCase};
+pub use traffic::{
+    GhDailyTraffic, GhTrafficClones, GhTrafficPath, GhTrafficReferrer, GhTrafficViews,
+};
+pub use workflow::{
+    GhWorkflow, GhWorkflowRun, GhWorkflowRunConclusion, GhWorkflowRunStatus, GhWorkflowState,
+};
+
+#[cfg(feature = "client")]
 use std::error;
+#[cfg(feature = "client")]
 use std::fmt;
+#[cfg(feature = "client")]
+use std::fs::File;
+#[cfg(feature = "client")]
+use std::io::BufReader;
+#[cfg(feature = "client")]
+use std::path::Path;
+use std::time::Duration;
+#[cfg(feature = "client")]
+use std::time::SystemTime;
 
+#[cfg(feature = "client")]
+use reqwest::header::{HeaderMap, CONTENT_TYPE, LINK};
+#[cfg(feature = "client")]
 use reqwest::StatusCode;
-use serde::Deserialize;
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer};
+#[cfg(feature = "client")]
 use urlencoding::encode;
 
 #[derive(Deserialize, Clone, Debug)]
@@ -83,12 +308,31 @@ pub struct GhRepoInfo {
 
     #[serde(rename = "html_url")]
     pub url: String,
+    /// HTTPS clone URL, e.g. `"https://github.com/rust-lang/rust.git"`.
+    pub clone_url: String,
+    /// SSH clone URL, e.g. `"git@github.com:rust-lang/rust.git"`.
+    pub ssh_url: String,
 
     pub owner: GhRepoOwnerInfo,
 
     pub stargazers_count: usize,
+    /// Not included when the repository is part of a listing,
+    /// in which case it is `0`.
+    #[serde(default)]
     pub subscribers_count: usize,
+    /// Number of direct forks of the repository.
     pub forks_count: usize,
+    /// Same as [`forks_count`](Self::forks_count), GitHub includes
+    /// both for backwards compatibility.
+    #[serde(default)]
+    pub forks: usize,
+    /// Number of forks in the whole fork network, i.e. including forks
+    /// of forks, and counted from the root repository if this is a fork.
+    ///
+    /// Not included when the repository is part of a listing,
+    /// in which case it is `0`.
+    #[serde(default)]
+    pub network_count: usize,
 
     /// Open Issues + Open PRs
     pub open_issues_count: usize,
@@ -97,15 +341,214 @@ pub struct GhRepoInfo {
     pub is_fork: bool,
     #[serde(rename = "archived")]
     pub is_archived: bool,
+    #[serde(rename = "private")]
+    pub is_private: bool,
+    #[serde(default)]
+    pub visibility: Option<GhRepoVisibility>,
+    /// Permissions of the authenticated user.
+    ///
+    /// Only present when the request is authenticated.
+    #[serde(default)]
+    pub permissions: Option<GhRepoPermissions>,
 
     pub default_branch: String,
 
+    pub has_issues: bool,
+    pub has_projects: bool,
+    pub has_wiki: bool,
+    pub has_pages: bool,
+    #[serde(default)]
+    pub has_downloads: bool,
+    #[serde(default)]
+    pub has_discussions: bool,
+
+    /// Empty if the repository has no homepage.
+    #[serde(default, deserialize_with = "null_as_default")]
     pub homepage: String,
-    pub description: String,
-    pub license: GhRepoLicenseInfo,
+    pub description: Option<String>,
+    /// `None` if the repository has no license, or if the license
+    /// could not be deserialized, e.g. due to missing fields.
+    #[serde(default, deserialize_with = "ok_or_none")]
+    pub license: Option<GhRepoLicenseInfo>,
 
-    pub language: String,
+    pub language: Option<String>,
+    #[serde(default)]
     pub topics: Vec<String>,
+
+    /// ISO 8601 timestamp, e.g. `"2011-01-26T19:01:12Z"`.
+    pub created_at: String,
+    /// ISO 8601 timestamp of when the repository itself was last
+    /// updated, e.g. its description.
+    pub updated_at: String,
+    /// ISO 8601 timestamp of the last push to any branch.
+    ///
+    /// `None` if nothing has been pushed to the repository.
+    pub pushed_at: Option<String>,
+}
+
+impl GhRepoInfo {
+    /// Deserializes a repository from JSON, e.g. the `repository`
+    /// of a webhook payload, or the output of `gh api repos/OWNER/REPO`.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Same as [`GhRepoInfo::from_json()`], but failing with
+    /// [`GhRepoInfoError::DeserializeJsonFailed`] like the [`Client`],
+    /// e.g. for a saved response used as a test fixture.
+    #[cfg(feature = "client")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+    pub fn from_json_str(json: &str) -> Result<Self, GhRepoInfoError> {
+        Self::from_json(json).map_err(|err| GhRepoInfoError::DeserializeJsonFailed(err, None))
+    }
+
+    /// Same as [`GhRepoInfo::from_json_str()`], but reading the JSON
+    /// from the file at `path`.
+    ///
+    /// Failing to read the file is returned as
+    /// [`GhRepoInfoError::DeserializeJsonFailed`] where
+    /// [`serde_json::Error::is_io()`] is `true`.
+    #[cfg(feature = "client")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self, GhRepoInfoError> {
+        File::open(path)
+            .map_err(serde_json::Error::io)
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)))
+            .map_err(|err| GhRepoInfoError::DeserializeJsonFailed(err, None))
+    }
+
+    /// Same as [`GhRepoInfo::from_json()`], but from an already
+    /// parsed [`serde_json::Value`].
+    pub fn from_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    /// Returns `true` if the repository was pushed to within
+    /// the given `duration`, i.e. `pushed_at` is more recent
+    /// than `now - duration`.
+    ///
+    /// Returns `false` if nothing has been pushed to the repository.
+    pub fn pushed_within(&self, duration: Duration) -> bool {
+        self.pushed_at
+            .as_deref()
+            .and_then(time::parse_timestamp)
+            .is_some_and(|pushed_at| time::elapsed_since(pushed_at) <= duration)
+    }
+
+    /// Returns how long ago the repository was created.
+    ///
+    /// Returns zero if `created_at` is in the future or
+    /// cannot be parsed.
+    pub fn age(&self) -> Duration {
+        time::parse_timestamp(&self.created_at)
+            .map(time::elapsed_since)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns `true` if the repository is archived, or has not been
+    /// pushed to within the given `threshold`, see [`pushed_within()`].
+    ///
+    /// [`pushed_within()`]: Self::pushed_within
+    pub fn is_likely_inactive(&self, threshold: Duration) -> bool {
+        self.is_archived || !self.pushed_within(threshold)
+    }
+
+    /// Returns the full name, description, and topics joined into a
+    /// single lowercase string, with whitespace normalized to single
+    /// spaces, e.g. for search indexing.
+    pub fn search_text(&self) -> String {
+        let description = self.description.as_deref().unwrap_or_default();
+        [self.full_name.as_str(), description]
+            .into_iter()
+            .chain(self.topics.iter().map(String::as_str))
+            .flat_map(str::split_whitespace)
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the homepage, or `None` if the repository has no
+    /// homepage, i.e. if [`homepage`](Self::homepage) is empty.
+    pub fn homepage_url(&self) -> Option<&str> {
+        let homepage = self.homepage.trim();
+        (!homepage.is_empty()).then_some(homepage)
+    }
+
+    /// Returns the topics lowercased, sorted, and deduplicated,
+    /// e.g. for comparing repositories or detecting changes.
+    pub fn topics_sorted(&self) -> Vec<String> {
+        let mut topics = self
+            .topics
+            .iter()
+            .map(|topic| topic.to_lowercase())
+            .collect::<Vec<_>>();
+        topics.sort_unstable();
+        topics.dedup();
+        topics
+    }
+
+    /// Returns the URL of the issues page, e.g.
+    /// `"https://github.com/rust-lang/rust/issues"`.
+    pub fn issues_url(&self) -> String {
+        self.page_url("issues")
+    }
+
+    /// Returns the URL of the pull requests page, e.g.
+    /// `"https://github.com/rust-lang/rust/pulls"`.
+    pub fn pulls_url(&self) -> String {
+        self.page_url("pulls")
+    }
+
+    /// Returns the URL of the releases page, e.g.
+    /// `"https://github.com/rust-lang/rust/releases"`.
+    pub fn releases_url(&self) -> String {
+        self.page_url("releases")
+    }
+
+    /// Returns the URL of the commits page of `branch`, e.g.
+    /// `"https://github.com/rust-lang/rust/commits/master"`,
+    /// or of the default branch if `branch` is `None`.
+    pub fn commits_url(&self, branch: Option<&str>) -> String {
+        let branch = branch.unwrap_or(&self.default_branch);
+        self.page_url(&format!("commits/{branch}"))
+    }
+
+    fn page_url(&self, page: &str) -> String {
+        format!("{}/{page}", self.url.trim_end_matches('/'))
+    }
+
+    /// Returns a `git clone` command for the repository, e.g.
+    /// `"git clone git@github.com:rust-lang/rust.git"`, optionally
+    /// cloning into `dir`.
+    ///
+    /// `dir` is included as is, i.e. it is not escaped for any shell.
+    pub fn clone_command(&self, protocol: CloneProtocol, dir: Option<&str>) -> String {
+        let url = match protocol {
+            CloneProtocol::Https => &self.clone_url,
+            CloneProtocol::Ssh => &self.ssh_url,
+        };
+        match dir {
+            Some(dir) => format!("git clone {url} {dir}"),
+            None => format!("git clone {url}"),
+        }
+    }
+
+    /// Returns the color of the primary language, see [`language_color()`].
+    pub fn language_color(&self) -> Option<&'static str> {
+        language_color(self.language.as_deref()?)
+    }
+
+    /// Returns the `has_*` fields grouped together.
+    pub fn features(&self) -> GhRepoFeatures {
+        GhRepoFeatures {
+            has_issues: self.has_issues,
+            has_projects: self.has_projects,
+            has_wiki: self.has_wiki,
+            has_pages: self.has_pages,
+            has_downloads: self.has_downloads,
+            has_discussions: self.has_discussions,
+        }
+    }
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -119,108 +562,873 @@ pub struct GhRepoOwnerInfo {
     pub kind: GhRepoOwnerKind,
 }
 
+impl GhRepoOwnerInfo {
+    /// Returns the [`avatar_url`](Self::avatar_url) with the `s` query
+    /// parameter set to `px`, for GitHub to scale the image to be
+    /// `px` pixels wide and tall.
+    ///
+    /// Any existing `s` parameter is replaced, other parameters
+    /// are kept, e.g. `?v=4` becomes `?v=4&s=64`.
+    pub fn avatar_url_sized(&self, px: u32) -> String {
+        let (url, query) = match self.avatar_url.split_once('?') {
+            Some((url, query)) => (url, query),
+            None => (self.avatar_url.as_str(), ""),
+        };
+        let mut params = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .filter(|param| param.split('=').next() != Some("s"))
+            .collect::<Vec<_>>();
+        let size = format!("s={px}");
+        params.push(&size);
+        format!("{url}?{}", params.join("&"))
+    }
+}
+
+/// A reference to a user, as included in listings.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::list_org_members()`](crate::Client::list_org_members)."
+)]
+pub type GhUserRef = GhRepoOwnerInfo;
+
 #[derive(Deserialize, Clone, Copy, Debug)]
 pub enum GhRepoOwnerKind {
     User,
     Organization,
+    Bot,
+}
+
+/// Protocol used by [`GhRepoInfo::clone_command()`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CloneProtocol {
+    /// See [`GhRepoInfo::clone_url`].
+    #[default]
+    Https,
+    /// See [`GhRepoInfo::ssh_url`].
+    Ssh,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum GhRepoVisibility {
+    Public,
+    Private,
+    /// Only visible to members of the enterprise.
+    Internal,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct GhRepoPermissions {
+    pub admin: bool,
+    #[serde(default)]
+    pub maintain: bool,
+    pub push: bool,
+    #[serde(default)]
+    pub triage: bool,
+    pub pull: bool,
+}
+
+/// Features enabled for a repository, see [`GhRepoInfo::features()`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct GhRepoFeatures {
+    pub has_issues: bool,
+    pub has_projects: bool,
+    pub has_wiki: bool,
+    pub has_pages: bool,
+    pub has_downloads: bool,
+    pub has_discussions: bool,
+}
+
+impl GhRepoFeatures {
+    /// Returns an iterator of each feature's name, e.g. `"issues"`,
+    /// and whether it is enabled.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, bool)> {
+        [
+            ("issues", self.has_issues),
+            ("projects", self.has_projects),
+            ("wiki", self.has_wiki),
+            ("pages", self.has_pages),
+            ("downloads", self.has_downloads),
+            ("discussions", self.has_discussions),
+        ]
+        .into_iter()
+    }
 }
 
+/// Unknown fields are ignored.
 #[derive(Deserialize, Clone, Debug)]
 pub struct GhRepoLicenseInfo {
     pub key: String,
     pub name: String,
+    /// `"NOASSERTION"` for licenses GitHub could not identify,
+    /// i.e. when [`key`](Self::key) is `"other"`.
+    #[serde(default)]
+    pub spdx_id: Option<String>,
+    /// API URL of the license, `None` for unidentified licenses.
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub node_id: Option<String>,
 }
 
 /// Get GitHub repository information given an `owner` and `repo`.
+///
+/// This is a shorthand for [`get_with()`] using [`RequestOptions::default()`].
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
 pub async fn get(
     owner: impl AsRef<str>,
     repo: impl AsRef<str>,
 ) -> Result<GhRepoInfo, GhRepoInfoError> {
-    let (owner, repo) = (owner.as_ref(), repo.as_ref());
-    let url = api_url(owner, repo);
+    get_with(owner, repo, &RequestOptions::default()).await
+}
 
-    let resp = reqwest::Client::new()
-        .get(url)
-        .header("User-Agent", env!("CARGO_PKG_NAME"))
-        .send()
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+/// Same as [`get()`], but taking the `owner` and `repo` as a
+/// single [`IntoRepoRef`], e.g. a tuple.
+///
+/// ```rust,no_run
+/// # async fn run() -> Result<(), gh_repo_info::GhRepoInfoError> {
+/// let repos = vec![("rust-lang".to_owned(), "rust".to_owned())];
+/// for repo in &repos {
+///     let repo = gh_repo_info::get_repo(repo).await?;
+///     println!("{} has {} stars", repo.full_name, repo.stargazers_count);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_repo(repo: impl IntoRepoRef) -> Result<GhRepoInfo, GhRepoInfoError> {
+    get(repo.owner(), repo.repo()).await
+}
+
+/// Same as [`get()`], but using the given `options`.
+///
+/// Each call builds a new [`Client`]. When making many requests,
+/// prefer building a `Client` once using [`ClientBuilder`].
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub async fn get_with(
+    owner: impl AsRef<str>,
+    repo: impl AsRef<str>,
+    options: &RequestOptions,
+) -> Result<GhRepoInfo, GhRepoInfoError> {
+    ClientBuilder::from(options).build()?.get(owner, repo).await
+}
+
+/// Same as [`get()`], but authenticated using the token stored by the
+/// `gh` CLI, see [`gh_cli_token()`].
+///
+/// Respects the `GH_HOST` environment variable, using
+/// `https://GH_HOST/api/v3` as the base URL for hosts other
+/// than `github.com`. If no token is found, the request is
+/// made unauthenticated.
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub async fn get_with_gh_auth(
+    owner: impl AsRef<str>,
+    repo: impl AsRef<str>,
+) -> Result<GhRepoInfo, GhRepoInfoError> {
+    ClientBuilder::from_gh_cli().build()?.get(owner, repo).await
+}
+
+/// Check that `token` is valid, returning the user it authenticates as.
+///
+/// This is a shorthand for [`ClientBuilder::token()`] followed by
+/// [`Client::verify_token()`].
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub async fn verify_token(token: impl Into<String>) -> Result<GhAuthInfo, GhRepoInfoError> {
+    ClientBuilder::new()
+        .token(token)
+        .build()?
+        .verify_token()
         .await
-        .map_err(GhRepoInfoError::SendRequest)?;
+}
 
-    let status = resp.status();
-    if !status.is_success() {
-        return Err(GhRepoInfoError::ResponseNonSuccess(status));
+/// Default predicate for [`Client::get_with_retries()`], used by
+/// [`Client::get_with_default_retries()`].
+///
+/// Retries timeouts, failures to send the request, and `5xx` server
+/// errors, for a total of 3 attempts.
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+pub fn default_should_retry(err: &GhRepoInfoError, attempt: u32) -> bool {
+    if attempt >= 3 {
+        return false;
+    }
+    match err {
+        GhRepoInfoError::Connect(_)
+        | GhRepoInfoError::SendRequest(_)
+        | GhRepoInfoError::Timeout(_) => true,
+        GhRepoInfoError::ResponseNonSuccess(code, _) => code.is_server_error(),
+        _ => false,
     }
+}
 
-    let repo = resp
-        .json::<GhRepoInfo>()
-        .await
-        .map_err(GhRepoInfoError::DeserializeFailed)?;
-    Ok(repo)
+/// Delay before retrying after the given failed `attempt`, starting
+/// at 500 milliseconds and doubling for each attempt, up to 30 seconds.
+#[cfg(feature = "client")]
+fn retry_delay(attempt: u32) -> Duration {
+    let exp = attempt.saturating_sub(1).min(6);
+    Duration::from_millis(500 << exp).min(Duration::from_secs(30))
 }
 
-/// The functionality in `gh_repo_info::blocking` must not be executed
-/// within an async runtime, or it will panic when attempting to block.
-#[cfg(feature = "blocking")]
-#[cfg_attr(doc_cfg, doc(cfg(feature = "blocking")))]
-pub mod blocking {
-    use super::{api_url, GhRepoInfo, GhRepoInfoError};
-
-    /// Get GitHub repository information given an `owner` and `repo`.
-    pub fn get(
-        owner: impl AsRef<str>,
-        repo: impl AsRef<str>,
-    ) -> Result<GhRepoInfo, GhRepoInfoError> {
-        let (owner, repo) = (owner.as_ref(), repo.as_ref());
-        let url = api_url(owner, repo);
-
-        let resp = reqwest::blocking::Client::new()
-            .get(url)
-            .header("User-Agent", env!("CARGO_PKG_NAME"))
-            .send()
-            .map_err(GhRepoInfoError::SendRequest)?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            return Err(GhRepoInfoError::ResponseNonSuccess(status));
-        }
+#[cfg(feature = "client")]
+const API_URL: &str = "https://api.github.com";
 
-        let repo = resp
-            .json::<GhRepoInfo>()
-            .map_err(GhRepoInfoError::DeserializeFailed)?;
-        Ok(repo)
-    }
+#[cfg(feature = "client")]
+fn api_url(base_url: &str, owner: impl AsRef<str>, repo: impl AsRef<str>) -> String {
+    let (owner, repo) = (owner.as_ref(), repo.as_ref());
+    let owner = encode(owner);
+    let repo = encode(repo);
+    format!("{base_url}/repos/{owner}/{repo}")
+}
+
+/// Splits a full name of the form `owner/repo`.
+#[cfg(feature = "client")]
+fn parse_full_name(full_name: &str) -> Result<(&str, &str), GhRepoInfoError> {
+    repo_ref::split_full_name(full_name)
+        .ok_or_else(|| GhRepoInfoError::InvalidFullName(full_name.to_owned()))
 }
 
-fn api_url(owner: impl AsRef<str>, repo: impl AsRef<str>) -> String {
+#[cfg(feature = "client")]
+fn starred_url(base_url: &str, owner: impl AsRef<str>, repo: impl AsRef<str>) -> String {
     let (owner, repo) = (owner.as_ref(), repo.as_ref());
     let owner = encode(owner);
     let repo = encode(repo);
-    format!("https://api.github.com/repos/{owner}/{repo}")
+    format!("{base_url}/user/starred/{owner}/{repo}")
+}
+
+#[cfg(feature = "client")]
+fn check_status(status: StatusCode, headers: &HeaderMap) -> Result<(), GhRepoInfoError> {
+    if !status.is_success() {
+        let request_id = request_id(headers);
+        if status == StatusCode::FORBIDDEN {
+            return Err(GhRepoInfoError::Forbidden(request_id));
+        }
+        if status == StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS {
+            return Err(GhRepoInfoError::UnavailableForLegalReasons(request_id));
+        }
+        return Err(GhRepoInfoError::ResponseNonSuccess(status, request_id));
+    }
+    Ok(())
+}
+
+/// Returns [`GhRepoInfoError::TokenExpired`] if a `401 Unauthorized`
+/// response says the token expired, otherwise
+/// [`GhRepoInfoError::Unauthorized`].
+///
+/// The token is considered expired if the
+/// `GitHub-Authentication-Token-Expiration` header is in the past,
+/// or if the message of the `body` says so.
+#[cfg(feature = "client")]
+fn unauthorized(headers: &HeaderMap, body: &[u8]) -> GhRepoInfoError {
+    #[derive(Deserialize)]
+    struct ErrorBody {
+        message: String,
+    }
+
+    let request_id = request_id(headers);
+    let expired = token_expiration(headers)
+        .is_some_and(|expiration| expiration <= SystemTime::now())
+        || serde_json::from_slice::<ErrorBody>(body)
+            .is_ok_and(|body| body.message.to_ascii_lowercase().contains("expired"));
+    if expired {
+        GhRepoInfoError::TokenExpired(request_id)
+    } else {
+        GhRepoInfoError::Unauthorized(request_id)
+    }
+}
+
+/// Returns the `GitHub-Authentication-Token-Expiration` header,
+/// e.g. `2024-02-29 12:00:00 UTC`, if any.
+#[cfg(feature = "client")]
+fn token_expiration(headers: &HeaderMap) -> Option<SystemTime> {
+    let expiration = headers
+        .get("GitHub-Authentication-Token-Expiration")?
+        .to_str()
+        .ok()?;
+    let (date, time) = expiration.trim().strip_suffix(" UTC")?.split_once(' ')?;
+    time::parse_timestamp(&format!("{date}T{time}Z"))
+}
+
+/// Returns [`GhRepoInfoError::UnexpectedContentType`] if the
+/// `Content-Type` header is present and not JSON.
+#[cfg(feature = "client")]
+fn check_content_type(headers: &HeaderMap) -> Result<(), GhRepoInfoError> {
+    let Some(content_type) = headers.get(CONTENT_TYPE) else {
+        return Ok(());
+    };
+    let content_type = String::from_utf8_lossy(content_type.as_bytes());
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    if mime.ends_with("/json") || mime.ends_with("+json") {
+        Ok(())
+    } else {
+        Err(GhRepoInfoError::UnexpectedContentType(
+            content_type.into_owned(),
+        ))
+    }
+}
+
+/// Returns the `X-OAuth-Scopes` header split into scopes.
+#[cfg(feature = "client")]
+fn oauth_scopes(headers: &HeaderMap) -> Vec<String> {
+    let Some(scopes) = headers.get("X-OAuth-Scopes") else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(scopes.as_bytes())
+        .split(',')
+        .map(str::trim)
+        .filter(|scope| !scope.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Returns the `X-GitHub-Request-Id` header, if any.
+#[cfg(feature = "client")]
+fn request_id(headers: &HeaderMap) -> Option<String> {
+    let request_id = headers.get("X-GitHub-Request-Id")?.to_str().ok()?;
+    Some(request_id.to_owned())
+}
+
+/// Returns the `rel="next"` URL of the `Link` header, if any.
+#[cfg(feature = "client")]
+fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        let is_next = params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#);
+        let url = url.trim().strip_prefix('<')?.strip_suffix('>')?;
+        is_next.then(|| url.to_owned())
+    })
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
 }
 
+/// Deserializes `T`, falling back to `None` if it fails,
+/// instead of failing the containing struct.
+fn ok_or_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OkOrNone<T> {
+        Ok(T),
+        Err(IgnoredAny),
+    }
+
+    match Option::<OkOrNone<T>>::deserialize(deserializer)? {
+        Some(OkOrNone::Ok(value)) => Ok(Some(value)),
+        Some(OkOrNone::Err(_)) | None => Ok(None),
+    }
+}
+
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
 #[derive(Debug)]
 pub enum GhRepoInfoError {
+    BuildClient(reqwest::Error),
+    /// The endpoint requires authentication, but the
+    /// [`Client`] was built without a token.
+    MissingToken,
+    /// The [`CancellationToken`] was cancelled.
+    Cancelled,
+    /// Connecting to the server failed, e.g. the DNS lookup failed
+    /// or there is no network, i.e. no response was received.
+    Connect(reqwest::Error),
     SendRequest(reqwest::Error),
-    ResponseNonSuccess(StatusCode),
-    DeserializeFailed(reqwest::Error),
+    /// The request or reading the response body timed out,
+    /// see [`ClientBuilder::timeout()`].
+    Timeout(reqwest::Error),
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    ResponseNonSuccess(StatusCode, Option<String>),
+    /// The response was `403 Forbidden`, e.g. the authenticated
+    /// user lacks the required permissions, or the rate limit
+    /// was exceeded.
+    ///
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    Forbidden(Option<String>),
+    /// The response was `401 Unauthorized` and GitHub reported the
+    /// token as expired, e.g. a GitHub App installation token, which
+    /// expires after an hour. The token is reported as expired by
+    /// the error message, or by a `GitHub-Authentication-Token-Expiration`
+    /// header in the past. Other `401` responses are returned as
+    /// [`GhRepoInfoError::Unauthorized`].
+    ///
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    TokenExpired(Option<String>),
+    /// The response was `401 Unauthorized`, e.g. the token is invalid
+    /// or was revoked, see [`GhRepoInfoError::TokenExpired`] for
+    /// expired tokens.
+    ///
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    Unauthorized(Option<String>),
+    /// The response was `451 Unavailable For Legal Reasons`,
+    /// e.g. the repository was taken down following a DMCA notice.
+    ///
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    UnavailableForLegalReasons(Option<String>),
+    /// The repository is empty, i.e. has no commits.
+    ///
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    EmptyRepository(Option<String>),
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    DeserializeFailed(reqwest::Error, Option<String>),
+    /// Deserializing a response failed, when it was not deserialized
+    /// by `reqwest`, e.g. when listing pages with the `blocking`
+    /// client or `ClientBuilder::stream_listings()`, or when using
+    /// a [`ResponseCache`]. Also returned by
+    /// [`GhRepoInfo::from_json_str()`] and [`GhRepoInfo::from_json_file()`].
+    ///
+    /// Includes the `X-GitHub-Request-Id` of the response, if any.
+    DeserializeJsonFailed(serde_json::Error, Option<String>),
+    /// A GraphQL query failed, including the error messages.
+    GraphQl(Vec<String>),
+    /// The given full name is not of the form `owner/repo`, see
+    /// [`ParseRepoIdError`].
+    InvalidFullName(String),
+    /// The response was not JSON, e.g. an HTML error page from a
+    /// proxy, including the `Content-Type` of the response.
+    UnexpectedContentType(String),
 }
 
+#[cfg(feature = "client")]
+impl GhRepoInfoError {
+    fn from_send(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err)
+        } else if err.is_connect() {
+            Self::Connect(err)
+        } else {
+            Self::SendRequest(err)
+        }
+    }
+
+    fn from_deserialize(err: reqwest::Error, request_id: Option<String>) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err)
+        } else {
+            Self::DeserializeFailed(err, request_id)
+        }
+    }
+
+    /// Returns the `X-GitHub-Request-Id` of the failing response,
+    /// which GitHub Support asks for when reporting issues.
+    ///
+    /// Returns `None` if the error did not originate from a response,
+    /// or the response did not include the header.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::BuildClient(_err) => None,
+            Self::MissingToken => None,
+            Self::Cancelled => None,
+            Self::SendRequest(_err) => None,
+            Self::Connect(_err) => None,
+            Self::Timeout(_err) => None,
+            Self::ResponseNonSuccess(_code, request_id) => request_id.as_deref(),
+            Self::Forbidden(request_id) => request_id.as_deref(),
+            Self::TokenExpired(request_id) => request_id.as_deref(),
+            Self::Unauthorized(request_id) => request_id.as_deref(),
+            Self::UnavailableForLegalReasons(request_id) => request_id.as_deref(),
+            Self::EmptyRepository(request_id) => request_id.as_deref(),
+            Self::DeserializeFailed(_err, request_id) => request_id.as_deref(),
+            Self::DeserializeJsonFailed(_err, request_id) => request_id.as_deref(),
+            Self::GraphQl(_messages) => None,
+            Self::InvalidFullName(_full_name) => None,
+            Self::UnexpectedContentType(_content_type) => None,
+        }
+    }
+
+    /// Returns the status of the failing response.
+    ///
+    /// Returns `None` if the error did not originate from a
+    /// non-successful response.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::BuildClient(_err) => None,
+            Self::MissingToken => None,
+            Self::Cancelled => None,
+            Self::SendRequest(_err) => None,
+            Self::Connect(_err) => None,
+            Self::Timeout(_err) => None,
+            Self::ResponseNonSuccess(code, _request_id) => Some(*code),
+            Self::Forbidden(_request_id) => Some(StatusCode::FORBIDDEN),
+            Self::TokenExpired(_request_id) => Some(StatusCode::UNAUTHORIZED),
+            Self::Unauthorized(_request_id) => Some(StatusCode::UNAUTHORIZED),
+            Self::UnavailableForLegalReasons(_request_id) => {
+                Some(StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS)
+            }
+            Self::EmptyRepository(_request_id) => Some(StatusCode::CONFLICT),
+            Self::DeserializeFailed(_err, _request_id) => None,
+            Self::DeserializeJsonFailed(_err, _request_id) => None,
+            Self::GraphQl(_messages) => None,
+            Self::InvalidFullName(_full_name) => None,
+            Self::UnexpectedContentType(_content_type) => None,
+        }
+    }
+
+    /// Returns the category of [`status()`](Self::status).
+    pub fn status_category(&self) -> Option<StatusCategory> {
+        let status = self.status()?;
+        if status.is_redirection() {
+            Some(StatusCategory::Redirect)
+        } else if status.is_client_error() {
+            Some(StatusCategory::ClientError)
+        } else if status.is_server_error() {
+            Some(StatusCategory::ServerError)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the request failed before a valid response
+    /// was received, i.e. sending the request failed, timed out, or
+    /// the response body was not JSON or could not be deserialized.
+    pub fn is_transport(&self) -> bool {
+        match self {
+            Self::BuildClient(_err) => false,
+            Self::MissingToken => false,
+            Self::Cancelled => false,
+            Self::SendRequest(_err) => true,
+            Self::Connect(_err) => true,
+            Self::Timeout(_err) => true,
+            Self::ResponseNonSuccess(_code, _request_id) => false,
+            Self::Forbidden(_request_id) => false,
+            Self::TokenExpired(_request_id) => false,
+            Self::Unauthorized(_request_id) => false,
+            Self::UnavailableForLegalReasons(_request_id) => false,
+            Self::EmptyRepository(_request_id) => false,
+            Self::DeserializeFailed(_err, _request_id) => true,
+            Self::DeserializeJsonFailed(_err, _request_id) => true,
+            Self::GraphQl(_messages) => false,
+            Self::InvalidFullName(_full_name) => false,
+            Self::UnexpectedContentType(_content_type) => true,
+        }
+    }
+
+    /// Returns `true` if GitHub responded with a non-successful status.
+    pub fn is_api(&self) -> bool {
+        match self {
+            Self::BuildClient(_err) => false,
+            Self::MissingToken => false,
+            Self::Cancelled => false,
+            Self::SendRequest(_err) => false,
+            Self::Connect(_err) => false,
+            Self::Timeout(_err) => false,
+            Self::ResponseNonSuccess(_code, _request_id) => true,
+            Self::Forbidden(_request_id) => true,
+            Self::TokenExpired(_request_id) => true,
+            Self::Unauthorized(_request_id) => true,
+            Self::UnavailableForLegalReasons(_request_id) => true,
+            Self::EmptyRepository(_request_id) => true,
+            Self::DeserializeFailed(_err, _request_id) => false,
+            Self::DeserializeJsonFailed(_err, _request_id) => false,
+            Self::GraphQl(_messages) => true,
+            Self::InvalidFullName(_full_name) => false,
+            Self::UnexpectedContentType(_content_type) => false,
+        }
+    }
+}
+
+/// Category of a non-successful status, see [`GhRepoInfoError::status_category()`].
+#[cfg(feature = "client")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "client")))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusCategory {
+    /// `3xx`, e.g. a redirect that was not followed,
+    /// see [`ClientBuilder::redirect()`].
+    Redirect,
+    /// `4xx`
+    ClientError,
+    /// `5xx`
+    ServerError,
+}
+
+#[cfg(feature = "client")]
 impl error::Error for GhRepoInfoError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
+            Self::BuildClient(err) => Some(err),
+            Self::MissingToken => None,
+            Self::Cancelled => None,
             Self::SendRequest(err) => Some(err),
-            Self::ResponseNonSuccess(_code) => None,
-            Self::DeserializeFailed(err) => Some(err),
+            Self::Connect(err) => Some(err),
+            Self::Timeout(err) => Some(err),
+            Self::ResponseNonSuccess(_code, _request_id) => None,
+            Self::Forbidden(_request_id) => None,
+            Self::TokenExpired(_request_id) => None,
+            Self::Unauthorized(_request_id) => None,
+            Self::UnavailableForLegalReasons(_request_id) => None,
+            Self::EmptyRepository(_request_id) => None,
+            Self::DeserializeFailed(err, _request_id) => Some(err),
+            Self::DeserializeJsonFailed(err, _request_id) => Some(err),
+            Self::GraphQl(_messages) => None,
+            Self::InvalidFullName(_full_name) => None,
+            Self::UnexpectedContentType(_content_type) => None,
         }
     }
 }
 
+#[cfg(feature = "client")]
 impl fmt::Display for GhRepoInfoError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::BuildClient(err) => write!(f, "building client failed: {err}"),
+            Self::MissingToken => write!(f, "endpoint requires a token"),
+            Self::Cancelled => write!(f, "request cancelled"),
+            Self::Connect(err) => write!(f, "connection failed: {err}"),
             Self::SendRequest(err) => write!(f, "send request failed: {err}"),
-            Self::ResponseNonSuccess(code) => write!(f, "response non-successful: {code}"),
-            Self::DeserializeFailed(err) => write!(f, "deserialization failed: {err}"),
+            Self::Timeout(err) => write!(f, "request timed out: {err}"),
+            Self::ResponseNonSuccess(code, _request_id) => {
+                write!(f, "response non-successful: {code}")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::Forbidden(_request_id) => {
+                write!(f, "forbidden")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::TokenExpired(_request_id) => {
+                write!(f, "token expired")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::Unauthorized(_request_id) => {
+                write!(f, "unauthorized")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::UnavailableForLegalReasons(_request_id) => {
+                write!(f, "unavailable for legal reasons")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::EmptyRepository(_request_id) => {
+                write!(f, "repository is empty")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::DeserializeFailed(err, _request_id) => {
+                write!(f, "deserialization failed: {err}")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::DeserializeJsonFailed(err, _request_id) => {
+                write!(f, "deserialization failed: {err}")?;
+                fmt_request_id(f, self.request_id())
+            }
+            Self::GraphQl(messages) => {
+                write!(f, "graphql query failed: {}", messages.join(", "))
+            }
+            Self::InvalidFullName(full_name) => {
+                write!(f, "invalid full name, expected owner/repo: {full_name:?}")
+            }
+            Self::UnexpectedContentType(content_type) => {
+                write!(f, "unexpected content type: {content_type:?}")
+            }
         }
     }
 }
+
+#[cfg(feature = "client")]
+fn fmt_request_id(f: &mut fmt::Formatter<'_>, request_id: Option<&str>) -> fmt::Result {
+    match request_id {
+        Some(request_id) => write!(f, " (request id: {request_id})"),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    pub(crate) fn repo_json() -> serde_json::Value {
+        json!({
+            "name": "rust",
+            "full_name": "rust-lang/rust",
+            "html_url": "https://github.com/rust-lang/rust",
+            "clone_url": "https://github.com/rust-lang/rust.git",
+            "ssh_url": "git@github.com:rust-lang/rust.git",
+            "owner": {
+                "login": "rust-lang",
+                "html_url": "https://github.com/rust-lang",
+                "avatar_url": "https://avatars.githubusercontent.com/u/5430905?v=4",
+                "type": "Organization"
+            },
+            "stargazers_count": 82127,
+            "subscribers_count": 1489,
+            "forks_count": 10830,
+            "forks": 10830,
+            "network_count": 10831,
+            "open_issues_count": 9549,
+            "fork": false,
+            "archived": false,
+            "private": false,
+            "default_branch": "master",
+            "has_issues": true,
+            "has_projects": true,
+            "has_wiki": true,
+            "has_pages": false,
+            "homepage": "https://www.rust-lang.org",
+            "description": "Empowering everyone to build reliable and efficient software.",
+            "language": "Rust",
+            "created_at": "2010-06-16T20:39:03Z",
+            "updated_at": "2023-04-27T11:04:53Z",
+            "pushed_at": "2023-04-27T10:35:25Z"
+        })
+    }
+
+    #[test]
+    fn from_json_forks() {
+        let repo = GhRepoInfo::from_json(&repo_json().to_string()).unwrap();
+        assert_eq!(repo.forks_count, 10830);
+        assert_eq!(repo.forks, 10830);
+        assert_eq!(repo.network_count, 10831);
+    }
+
+    #[test]
+    fn from_json_forks_listing() {
+        // Listings only include `forks_count` and `forks`
+        let mut json = repo_json();
+        let object = json.as_object_mut().unwrap();
+        object.remove("subscribers_count");
+        object.remove("network_count");
+
+        let repo = GhRepoInfo::from_json(&json.to_string()).unwrap();
+        assert_eq!(repo.forks_count, 10830);
+        assert_eq!(repo.forks, 10830);
+        assert_eq!(repo.network_count, 0);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn from_json_str() {
+        let repo = GhRepoInfo::from_json_str(&repo_json().to_string()).unwrap();
+        assert_eq!(repo.full_name, "rust-lang/rust");
+
+        let res = GhRepoInfo::from_json_str(r#"{"name":"rust"}"#);
+        assert!(
+            matches!(&res, Err(GhRepoInfoError::DeserializeJsonFailed(err, None)) if err.is_data()),
+            "{res:?}"
+        );
+        let res = GhRepoInfo::from_json_str("{");
+        assert!(
+            matches!(&res, Err(GhRepoInfoError::DeserializeJsonFailed(err, None)) if err.is_eof()),
+            "{res:?}"
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn from_json_file() {
+        let path = std::env::temp_dir().join(format!("gh-repo-info-{}.json", std::process::id()));
+        std::fs::write(&path, repo_json().to_string()).unwrap();
+        let repo = GhRepoInfo::from_json_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(repo.unwrap().full_name, "rust-lang/rust");
+
+        let res = GhRepoInfo::from_json_file(&path);
+        assert!(
+            matches!(&res, Err(GhRepoInfoError::DeserializeJsonFailed(err, None)) if err.is_io()),
+            "{res:?}"
+        );
+    }
+
+    fn parse_license(license: serde_json::Value) -> Option<GhRepoLicenseInfo> {
+        let mut json = repo_json();
+        json["license"] = license;
+        GhRepoInfo::from_json(&json.to_string()).unwrap().license
+    }
+
+    #[test]
+    fn from_json_license() {
+        let license = parse_license(json!({
+            "key": "mit",
+            "name": "MIT License",
+            "spdx_id": "MIT",
+            "url": "https://api.github.com/licenses/mit",
+            "node_id": "MDc6TGljZW5zZTEz",
+            "featured": true
+        }))
+        .unwrap();
+        assert_eq!(license.key, "mit");
+        assert_eq!(license.name, "MIT License");
+        assert_eq!(license.spdx_id.as_deref(), Some("MIT"));
+
+        assert!(parse_license(json!(null)).is_none());
+    }
+
+    #[test]
+    fn from_json_license_noassertion() {
+        let license = parse_license(json!({
+            "key": "other",
+            "name": "Other",
+            "spdx_id": "NOASSERTION",
+            "url": null,
+            "node_id": "MDc6TGljZW5zZTA="
+        }))
+        .unwrap();
+        assert_eq!(license.key, "other");
+        assert_eq!(license.spdx_id.as_deref(), Some("NOASSERTION"));
+        assert_eq!(license.url, None);
+    }
+
+    #[test]
+    fn from_json_license_fallback() {
+        // Missing `name`
+        assert!(parse_license(json!({ "key": "mit" })).is_none());
+        assert!(parse_license(json!("MIT")).is_none());
+        assert!(parse_license(json!(["mit"])).is_none());
+    }
+
+    fn owner_with_avatar(avatar_url: &str) -> GhRepoOwnerInfo {
+        GhRepoOwnerInfo {
+            name: "rust-lang".to_owned(),
+            url: "https://github.com/rust-lang".to_owned(),
+            avatar_url: avatar_url.to_owned(),
+            kind: GhRepoOwnerKind::Organization,
+        }
+    }
+
+    #[test]
+    fn avatar_url_sized() {
+        let owner = owner_with_avatar("https://avatars.githubusercontent.com/u/5430905");
+        assert_eq!(
+            owner.avatar_url_sized(64),
+            "https://avatars.githubusercontent.com/u/5430905?s=64"
+        );
+    }
+
+    #[test]
+    fn avatar_url_sized_query() {
+        let owner = owner_with_avatar("https://avatars.githubusercontent.com/u/5430905?v=4");
+        assert_eq!(
+            owner.avatar_url_sized(64),
+            "https://avatars.githubusercontent.com/u/5430905?v=4&s=64"
+        );
+    }
+
+    #[test]
+    fn avatar_url_sized_replaces_size() {
+        let owner = owner_with_avatar("https://avatars.githubusercontent.com/u/5430905?s=32&v=4");
+        assert_eq!(
+            owner.avatar_url_sized(64),
+            "https://avatars.githubusercontent.com/u/5430905?v=4&s=64"
+        );
+
+        let owner = owner_with_avatar("https://avatars.githubusercontent.com/u/5430905?v=4&s=32");
+        assert_eq!(
+            owner.avatar_url_sized(64),
+            "https://avatars.githubusercontent.com/u/5430905?v=4&s=64"
+        );
+    }
+}
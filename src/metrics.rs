@@ -0,0 +1,102 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use super::time;
+
+/// Outcome of a single request, passed to the callback given
+/// to [`ClientBuilder::on_request()`](crate::ClientBuilder::on_request).
+#[derive(Clone, Debug)]
+pub struct RequestMetrics {
+    pub url: String,
+    /// `None` if no response was received, e.g. the request timed out.
+    pub status: Option<StatusCode>,
+    /// Time until the response headers were received,
+    /// or until the request failed.
+    pub duration: Duration,
+    /// The `X-RateLimit-Remaining` header of the response, if any.
+    pub rate_limit_remaining: Option<u64>,
+    /// The `X-RateLimit-Reset` header of the response, if any.
+    pub rate_limit_reset: Option<SystemTime>,
+    /// The `Date` header of the response, if any, i.e. the
+    /// current time according to GitHub.
+    pub server_date: Option<SystemTime>,
+    /// The `Deprecation` header of the response, if any, i.e. the
+    /// endpoint or API version used by the request is deprecated.
+    pub deprecation: Option<String>,
+    /// The `Sunset` header of the response, if any, i.e. the
+    /// date when the endpoint or API version will be removed.
+    pub sunset: Option<String>,
+}
+
+impl RequestMetrics {
+    pub(crate) fn new(
+        url: String,
+        status: Option<StatusCode>,
+        headers: Option<&HeaderMap>,
+        duration: Duration,
+    ) -> Self {
+        let header = |name: &str| {
+            headers
+                .and_then(|headers| headers.get(name))
+                .and_then(|value| value.to_str().ok())
+        };
+        let rate_limit_remaining =
+            header("X-RateLimit-Remaining").and_then(|value| value.parse().ok());
+        let rate_limit_reset = header("X-RateLimit-Reset")
+            .and_then(|value| value.parse().ok())
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let server_date = header("Date").and_then(time::parse_http_date);
+        let deprecation = header("Deprecation").map(str::to_owned);
+        let sunset = header("Sunset").map(str::to_owned);
+        Self {
+            url,
+            status,
+            duration,
+            rate_limit_remaining,
+            rate_limit_reset,
+            server_date,
+            deprecation,
+            sunset,
+        }
+    }
+
+    /// Returns `true` if the response included a `Deprecation`
+    /// or `Sunset` header.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation.is_some() || self.sunset.is_some()
+    }
+
+    /// Returns how long until the rate limit resets, relative to
+    /// [`server_date`](Self::server_date) instead of the local clock,
+    /// to avoid clock skew.
+    ///
+    /// Returns `None` if either header is missing.
+    pub fn rate_limit_reset_after(&self) -> Option<Duration> {
+        let reset = self.rate_limit_reset?;
+        let server_date = self.server_date?;
+        Some(reset.duration_since(server_date).unwrap_or(Duration::ZERO))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct MetricsHook(Arc<dyn Fn(&RequestMetrics) + Send + Sync>);
+
+impl MetricsHook {
+    pub(crate) fn new(f: impl Fn(&RequestMetrics) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    pub(crate) fn call(&self, metrics: &RequestMetrics) {
+        (self.0)(metrics)
+    }
+}
+
+impl fmt::Debug for MetricsHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsHook")
+    }
+}
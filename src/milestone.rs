@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhMilestone {
+    pub number: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: GhMilestoneState,
+
+    #[serde(rename = "html_url")]
+    pub url: String,
+
+    pub open_issues: usize,
+    pub closed_issues: usize,
+
+    /// ISO 8601 timestamp, e.g. `"2012-10-09T23:39:01Z"`.
+    pub due_on: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum GhMilestoneState {
+    Open,
+    Closed,
+}
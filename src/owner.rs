@@ -0,0 +1,49 @@
+use serde::Deserialize;
+
+use super::GhRepoOwnerKind;
+
+/// Profile of a user or organization.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_owner_profile()`](crate::Client::get_owner_profile)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhOwnerProfile {
+    #[serde(rename = "login")]
+    pub name: String,
+    /// Display name, e.g. `"The Rust Programming Language"`.
+    #[serde(rename = "name")]
+    pub display_name: Option<String>,
+    /// Description of an organization, or bio of a user.
+    #[serde(default, alias = "bio")]
+    pub description: Option<String>,
+    #[serde(rename = "html_url")]
+    pub url: String,
+    pub avatar_url: String,
+    #[serde(rename = "type")]
+    pub kind: GhRepoOwnerKind,
+    pub public_repos: usize,
+    pub followers: usize,
+    /// Whether the organization has verified its domains,
+    /// `None` for users.
+    #[serde(default)]
+    pub is_verified: Option<bool>,
+    /// Only included when authenticated as the user,
+    /// or as an owner of the organization.
+    #[serde(default)]
+    pub plan: Option<GhOwnerPlan>,
+}
+
+/// GitHub plan of a user or organization, see [`GhOwnerProfile::plan`].
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhOwnerPlan {
+    /// Name of the plan, e.g. `"free"` or `"team"`.
+    pub name: String,
+    /// Number of paid seats, `None` for users.
+    #[serde(default)]
+    pub seats: Option<usize>,
+    /// Number of used seats, `None` for users.
+    #[serde(default)]
+    pub filled_seats: Option<usize>,
+}
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhPagesInfo {
+    /// URL of the published site, e.g. `"https://octocat.github.io/Hello-World/"`.
+    #[serde(rename = "html_url")]
+    pub url: String,
+    /// `None` if the site has not been built yet.
+    pub status: Option<GhPagesStatus>,
+    pub cname: Option<String>,
+    #[serde(default)]
+    pub https_enforced: bool,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum GhPagesStatus {
+    Built,
+    Building,
+    Errored,
+}
@@ -0,0 +1,115 @@
+use std::error;
+use std::fmt;
+
+/// Page size and page of listings, see
+/// [`Client::with_pagination()`](crate::Client::with_pagination).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Pagination {
+    per_page: u8,
+    page: Option<u32>,
+}
+
+impl Pagination {
+    /// Fetches `per_page` items per request, and all pages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `per_page` is zero or greater than 100,
+    /// which is the maximum allowed by GitHub, see
+    /// [`Pagination::try_new()`] for a fallible version.
+    pub fn new(per_page: u8) -> Self {
+        match Self::try_new(per_page) {
+            Ok(pagination) => pagination,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Same as [`Pagination::new()`], but returning an error instead
+    /// of panicking if `per_page` is zero or greater than 100.
+    pub fn try_new(per_page: u8) -> Result<Self, InvalidPerPage> {
+        if !(1..=100).contains(&per_page) {
+            return Err(InvalidPerPage(per_page));
+        }
+        Ok(Self {
+            per_page,
+            page: None,
+        })
+    }
+
+    /// Only fetch the given `page`, starting at 1,
+    /// instead of following the `Link` header to fetch all pages.
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub fn per_page(&self) -> u8 {
+        self.per_page
+    }
+
+    /// Returns `None` if all pages are fetched.
+    pub fn get_page(&self) -> Option<u32> {
+        self.page
+    }
+
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("per_page", self.per_page.to_string())];
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        query
+    }
+}
+
+impl Default for Pagination {
+    /// 100 items per page, and all pages.
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+/// The `per_page` given to [`Pagination::try_new()`] is zero
+/// or greater than 100.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidPerPage(u8);
+
+impl InvalidPerPage {
+    /// Returns the `per_page` that was given.
+    pub fn per_page(&self) -> u8 {
+        self.0
+    }
+}
+
+impl error::Error for InvalidPerPage {}
+
+impl fmt::Display for InvalidPerPage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "per_page must be between 1 and 100, got {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_new() {
+        assert_eq!(Pagination::try_new(0), Err(InvalidPerPage(0)));
+        assert_eq!(Pagination::try_new(1).unwrap().per_page(), 1);
+        assert_eq!(Pagination::try_new(100).unwrap().per_page(), 100);
+        assert_eq!(Pagination::try_new(101), Err(InvalidPerPage(101)));
+        assert_eq!(Pagination::try_new(u8::MAX), Err(InvalidPerPage(u8::MAX)));
+    }
+
+    #[test]
+    #[should_panic(expected = "per_page must be between 1 and 100, got 0")]
+    fn new_zero() {
+        Pagination::new(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "per_page must be between 1 and 100, got 101")]
+    fn new_too_large() {
+        Pagination::new(101);
+    }
+}
@@ -0,0 +1,13 @@
+//! Re-exports of the commonly used types and functions.
+//!
+//! ```rust
+//! use gh_repo_info::prelude::*;
+//! ```
+
+pub use crate::{
+    GhRepoFeatures, GhRepoInfo, GhRepoLicenseInfo, GhRepoOwnerInfo, GhRepoOwnerKind,
+    GhRepoPermissions, GhRepoSummary, GhRepoVisibility, GhUserRef,
+};
+
+#[cfg(feature = "client")]
+pub use crate::{get, Client, ClientBuilder, GhRepoInfoError, RequestOptions};
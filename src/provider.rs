@@ -0,0 +1,53 @@
+use futures_util::future::BoxFuture;
+
+use super::{Client, GhRepoInfo, GhRepoInfoError};
+
+/// Object-safe source of repository information, allowing
+/// implementations to be swapped at runtime, e.g. for tests.
+///
+/// The futures are boxed, such that `Box<dyn RepoInfoProvider>`
+/// can be stored and called without knowing the implementation.
+///
+/// ```rust,no_run
+/// use futures_util::future::BoxFuture;
+/// use gh_repo_info::{Client, GhRepoInfo, GhRepoInfoError, RepoInfoProvider};
+///
+/// struct Fixture(GhRepoInfo);
+///
+/// impl RepoInfoProvider for Fixture {
+///     fn get<'a>(
+///         &'a self,
+///         _owner: &'a str,
+///         _repo: &'a str,
+///     ) -> BoxFuture<'a, Result<GhRepoInfo, GhRepoInfoError>> {
+///         Box::pin(async move { Ok(self.0.clone()) })
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), GhRepoInfoError> {
+/// let provider: Box<dyn RepoInfoProvider> = Box::new(Client::new());
+///
+/// let info = provider.get("rust-lang", "rust").await?;
+/// println!("{}", info.stargazers_count);
+/// # Ok(())
+/// # }
+/// ```
+pub trait RepoInfoProvider: Send + Sync {
+    /// See [`Client::get()`].
+    fn get<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+    ) -> BoxFuture<'a, Result<GhRepoInfo, GhRepoInfoError>>;
+}
+
+impl RepoInfoProvider for Client {
+    fn get<'a>(
+        &'a self,
+        owner: &'a str,
+        repo: &'a str,
+    ) -> BoxFuture<'a, Result<GhRepoInfo, GhRepoInfoError>> {
+        Box::pin(Client::get(self, owner, repo))
+    }
+}
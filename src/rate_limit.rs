@@ -0,0 +1,63 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting the rate of outgoing requests, attached
+/// using [`ClientBuilder::rate_limiter()`](crate::ClientBuilder::rate_limiter).
+///
+/// Clones share the same bucket, so a single `RateLimiter` can be
+/// attached to multiple clients to limit all of them together.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    state: Arc<Mutex<Bucket>>,
+    capacity: f64,
+    /// Tokens per second.
+    rate: f64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    /// Allows `requests` requests per `period`, e.g. 30 per minute,
+    /// with bursts of up to `requests` requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests` or `period` is zero.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        assert!(requests > 0, "requests must be non-zero");
+        assert!(!period.is_zero(), "period must be non-zero");
+
+        let capacity = f64::from(requests);
+        Self {
+            state: Arc::new(Mutex::new(Bucket {
+                tokens: capacity,
+                last: Instant::now(),
+            })),
+            capacity,
+            rate: capacity / period.as_secs_f64(),
+        }
+    }
+
+    /// Takes a token, returning how long to wait before sending the
+    /// request. Tokens can be taken in advance, such that concurrent
+    /// requests are queued in the order they were reserved.
+    pub(crate) fn reserve(&self) -> Duration {
+        let mut bucket = self.state.lock().unwrap();
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+        bucket.last = now;
+
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        }
+    }
+}
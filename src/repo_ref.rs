@@ -0,0 +1,173 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An `(owner, repo)` pair identifying a repository,
+/// e.g. `("rust-lang", "rust")`.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`get_repo()`](crate::get_repo)."
+)]
+pub trait IntoRepoRef {
+    fn owner(&self) -> &str;
+    fn repo(&self) -> &str;
+}
+
+impl<A, B> IntoRepoRef for (A, B)
+where
+    A: AsRef<str>,
+    B: AsRef<str>,
+{
+    fn owner(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    fn repo(&self) -> &str {
+        self.1.as_ref()
+    }
+}
+
+impl<T> IntoRepoRef for &T
+where
+    T: IntoRepoRef + ?Sized,
+{
+    fn owner(&self) -> &str {
+        (**self).owner()
+    }
+
+    fn repo(&self) -> &str {
+        (**self).repo()
+    }
+}
+
+/// An owned and validated `owner/repo` pair, e.g. parsed
+/// from `"rust-lang/rust"`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct RepoId {
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RepoId {
+    pub fn new(owner: impl Into<String>, repo: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+}
+
+impl FromStr for RepoId {
+    type Err = ParseRepoIdError;
+
+    fn from_str(full_name: &str) -> Result<Self, Self::Err> {
+        match split_full_name(full_name) {
+            Some((owner, repo)) => Ok(Self::new(owner, repo)),
+            None => Err(ParseRepoIdError(full_name.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for RepoId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repo)
+    }
+}
+
+impl IntoRepoRef for RepoId {
+    fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    fn repo(&self) -> &str {
+        &self.repo
+    }
+}
+
+/// The string parsed as a [`RepoId`] is not of the form `owner/repo`,
+/// or either part contains characters other than ASCII alphanumerics,
+/// `.`, `-`, and `_`, e.g. whitespace.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseRepoIdError(String);
+
+impl ParseRepoIdError {
+    /// Returns the string that failed to parse.
+    pub fn input(&self) -> &str {
+        &self.0
+    }
+}
+
+impl error::Error for ParseRepoIdError {}
+
+impl fmt::Display for ParseRepoIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid full name, expected owner/repo: {:?}", self.0)
+    }
+}
+
+/// Splits a full name of the form `owner/repo`, returning `None`
+/// if either part is not a valid name, see [`is_valid_name()`].
+pub(crate) fn split_full_name(full_name: &str) -> Option<(&str, &str)> {
+    let (owner, repo) = full_name.split_once('/')?;
+    if is_valid_name(owner) && is_valid_name(repo) {
+        Some((owner, repo))
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if `name` is non-empty, only contains ASCII
+/// alphanumerics, `.`, `-`, and `_`, and is not `.` or `..`.
+///
+/// This covers the names GitHub allows for both owners and
+/// repositories, and ensures `name` is a single path segment.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && (name != ".")
+        && (name != "..")
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let id: RepoId = "rust-lang/rust".parse().unwrap();
+        assert_eq!(id, RepoId::new("rust-lang", "rust"));
+        assert_eq!(id.to_string(), "rust-lang/rust");
+
+        let id: RepoId = "user_1/my.repo-name".parse().unwrap();
+        assert_eq!(id, RepoId::new("user_1", "my.repo-name"));
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for full_name in ["", "a", "/b", "a/", "/", "a/b/c", "a//b"] {
+            let err = full_name.parse::<RepoId>().unwrap_err();
+            assert_eq!(err.input(), full_name);
+        }
+    }
+
+    #[test]
+    fn parse_whitespace() {
+        for full_name in [
+            " a / b ", "a /b", "a/ b", "a/b\n", "a/b\r\n", "\ta/b", "a/b c",
+        ] {
+            assert!(full_name.parse::<RepoId>().is_err(), "{full_name:?}");
+        }
+    }
+
+    #[test]
+    fn parse_invalid_characters() {
+        for full_name in [
+            "a/..", "a/.", "../b", "a/b?c", "a/b#c", "a/b%2F", "a/b\\c", "a/bé",
+        ] {
+            assert!(full_name.parse::<RepoId>().is_err(), "{full_name:?}");
+        }
+    }
+}
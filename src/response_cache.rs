@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A cached response body together with its `ETag`, see [`ResponseCache`].
+#[derive(Clone, Debug)]
+pub struct CachedEntry {
+    /// The `ETag` header of the response.
+    pub etag: String,
+    /// The JSON body of the response.
+    pub body: Vec<u8>,
+}
+
+/// Storage for responses, attached using
+/// [`ClientBuilder::cache()`](crate::ClientBuilder::cache).
+///
+/// The `Client` handles revalidation, i.e. sends the cached `ETag`
+/// as `If-None-Match`, and uses the cached body when GitHub responds
+/// with `304 Not Modified`. Implementations only store entries,
+/// e.g. in memory, see [`MemoryCache`], or in an external cache.
+///
+/// Keys are request URLs. Responses depend on the token, so a cache
+/// should not be shared between clients using different tokens.
+pub trait ResponseCache: Send + Sync {
+    /// Returns the entry stored for `key`, if any.
+    fn get(&self, key: &str) -> Option<CachedEntry>;
+
+    /// Stores `entry` for `key`, replacing any previous entry.
+    fn put(&self, key: &str, entry: CachedEntry);
+}
+
+/// Unbounded in-memory [`ResponseCache`].
+#[derive(Default, Debug)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, CachedEntry>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedEntry> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn put(&self, key: &str, entry: CachedEntry) {
+        self.entries.lock().unwrap().insert(key.to_owned(), entry);
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct CacheHandle(Arc<dyn ResponseCache>);
+
+impl CacheHandle {
+    pub(crate) fn new(cache: impl ResponseCache + 'static) -> Self {
+        Self(Arc::new(cache))
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<CachedEntry> {
+        self.0.get(key)
+    }
+
+    pub(crate) fn put(&self, key: &str, entry: CachedEntry) {
+        self.0.put(key, entry)
+    }
+}
+
+impl fmt::Debug for CacheHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CacheHandle")
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use super::{time, GhRepoInfo};
+
+/// Weights of [`GhRepoInfo::popularity_score_with()`].
+///
+/// The score is computed as:
+///
+/// ```text
+/// stars * ln(1 + stargazers_count)
+///     + forks * ln(1 + forks_count)
+///     + recency * 0.5^(time since pushed_at / recency_half_life)
+/// ```
+///
+/// Counts are logarithmic, such that a few very popular repositories
+/// do not dwarf everything else.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ScoreWeights {
+    /// Default is `1.0`.
+    pub stars: f64,
+    /// Default is `0.5`.
+    pub forks: f64,
+    /// Given in full if pushed to just now, and halved for every
+    /// `recency_half_life` since, or `0.0` if never pushed to.
+    ///
+    /// Default is `2.0`.
+    pub recency: f64,
+    /// Default is 180 days.
+    pub recency_half_life: Duration,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            stars: 1.0,
+            forks: 0.5,
+            recency: 2.0,
+            recency_half_life: Duration::from_secs(180 * 24 * 60 * 60),
+        }
+    }
+}
+
+impl GhRepoInfo {
+    /// Returns a score for ranking repositories by popularity,
+    /// using [`ScoreWeights::default()`].
+    ///
+    /// Only the already fetched fields are used.
+    pub fn popularity_score(&self) -> f64 {
+        self.popularity_score_with(&ScoreWeights::default())
+    }
+
+    /// Same as [`GhRepoInfo::popularity_score()`], but using the given `weights`.
+    pub fn popularity_score_with(&self, weights: &ScoreWeights) -> f64 {
+        let stars = (self.stargazers_count as f64).ln_1p();
+        let forks = (self.forks_count as f64).ln_1p();
+
+        let half_life = weights.recency_half_life.as_secs_f64();
+        let recency = self
+            .pushed_at
+            .as_deref()
+            .and_then(time::parse_timestamp)
+            .map(|pushed_at| {
+                let elapsed = time::elapsed_since(pushed_at).as_secs_f64();
+                if half_life > 0.0 {
+                    0.5f64.powf(elapsed / half_life)
+                } else {
+                    0.0
+                }
+            })
+            .unwrap_or(0.0);
+
+        (weights.stars * stars) + (weights.forks * forks) + (weights.recency * recency)
+    }
+}
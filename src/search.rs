@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+/// A page of search results.
+#[derive(Deserialize, Clone, Debug)]
+pub struct SearchResults<T> {
+    /// Total number of matches, of which at most 1000 can be retrieved.
+    pub total_count: usize,
+    /// `true` if the search timed out before all matches were found.
+    pub incomplete_results: bool,
+    pub items: Vec<T>,
+}
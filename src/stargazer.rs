@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+use super::GhUserRef;
+
+/// A user who starred a repository.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_stargazers()`](crate::Client::get_stargazers)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(from = "RawStargazer")]
+pub struct GhStargazer {
+    pub user: GhUserRef,
+    /// ISO 8601 timestamp, e.g. `"2011-01-26T19:01:12Z"`.
+    ///
+    /// `None` if the response was not requested using the
+    /// `application/vnd.github.star+json` media type.
+    pub starred_at: Option<String>,
+}
+
+/// The `star+json` media type wraps each user together with
+/// `starred_at`, otherwise the user is returned as is.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawStargazer {
+    Starred { starred_at: String, user: GhUserRef },
+    User(GhUserRef),
+}
+
+impl From<RawStargazer> for GhStargazer {
+    fn from(raw: RawStargazer) -> Self {
+        match raw {
+            RawStargazer::Starred { starred_at, user } => Self {
+                user,
+                starred_at: Some(starred_at),
+            },
+            RawStargazer::User(user) => Self {
+                user,
+                starred_at: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use super::GhRepoInfo;
+
+/// Minimal subset of [`GhRepoInfo`], for re-exposing repository
+/// information with a stable JSON shape.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GhRepoSummary {
+    pub name: String,
+    pub full_name: String,
+    pub url: String,
+    pub stars: usize,
+    pub forks: usize,
+    pub language: Option<String>,
+    pub description: Option<String>,
+}
+
+impl GhRepoSummary {
+    /// Returns a wrapper serializing this summary with camelCase
+    /// keys, e.g. `fullName`, for JavaScript consumers.
+    #[inline]
+    pub fn camel_case(&self) -> GhRepoSummaryCamelCase<'_> {
+        GhRepoSummaryCamelCase {
+            name: &self.name,
+            full_name: &self.full_name,
+            url: &self.url,
+            stars: self.stars,
+            forks: self.forks,
+            language: self.language.as_deref(),
+            description: self.description.as_deref(),
+        }
+    }
+}
+
+/// [`GhRepoSummary`] serialized with camelCase keys,
+/// see [`GhRepoSummary::camel_case()`].
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GhRepoSummaryCamelCase<'a> {
+    name: &'a str,
+    full_name: &'a str,
+    url: &'a str,
+    stars: usize,
+    forks: usize,
+    language: Option<&'a str>,
+    description: Option<&'a str>,
+}
+
+impl From<&GhRepoInfo> for GhRepoSummary {
+    fn from(repo: &GhRepoInfo) -> Self {
+        Self {
+            name: repo.name.clone(),
+            full_name: repo.full_name.clone(),
+            url: repo.url.clone(),
+            stars: repo.stargazers_count,
+            forks: repo.forks_count,
+            language: repo.language.clone(),
+            description: repo.description.clone(),
+        }
+    }
+}
+
+impl From<GhRepoInfo> for GhRepoSummary {
+    fn from(repo: GhRepoInfo) -> Self {
+        Self {
+            name: repo.name,
+            full_name: repo.full_name,
+            url: repo.url,
+            stars: repo.stargazers_count,
+            forks: repo.forks_count,
+            language: repo.language,
+            description: repo.description,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use crate::tests::repo_json;
+
+/// Serves `response` verbatim to every request, returning the
+/// base URL of the server.
+pub(crate) fn serve(response: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                break;
+            };
+            read_request(&mut stream);
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    base_url
+}
+
+/// Serves `responses` verbatim in order, one per request, returning
+/// the base URL of the server and the heads of the received requests.
+pub(crate) fn serve_sequence(responses: Vec<String>) -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for (stream, response) in listener.incoming().zip(responses) {
+            let Ok(mut stream) = stream else {
+                break;
+            };
+            let _ = tx.send(read_request(&mut stream));
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (base_url, rx)
+}
+
+/// Serves `head` to every request, and then stalls without
+/// closing the connection, returning the base URL of the server.
+///
+/// If `head` is empty, then the server never responds.
+pub(crate) fn serve_stalled(head: impl Into<String>) -> String {
+    let head = head.into();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        let mut streams = Vec::new();
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                break;
+            };
+            read_request(&mut stream);
+            let _ = stream.write_all(head.as_bytes());
+            streams.push(stream);
+        }
+    });
+    base_url
+}
+
+/// Reads the head of a request, i.e. until the empty line.
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.ends_with(b"\r\n\r\n") {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+    String::from_utf8_lossy(&request).into_owned()
+}
+
+/// A `200 OK` response with the repository and an `ETag`.
+pub(crate) fn repo_ok() -> String {
+    let body = repo_json().to_string();
+    format!(
+        concat!(
+            "HTTP/1.1 200 OK\r\n",
+            "Content-Type: application/json\r\n",
+            "ETag: \"abc\"\r\n",
+            "Content-Length: {}\r\n",
+            "Connection: close\r\n",
+            "\r\n",
+            "{}",
+        ),
+        body.len(),
+        body,
+    )
+}
@@ -0,0 +1,165 @@
+use std::time::{Duration, SystemTime};
+
+/// Parses an ISO 8601 UTC timestamp as returned by GitHub,
+/// e.g. `"2011-01-26T19:06:43Z"`.
+///
+/// Fractional seconds and offsets other than `Z` are not
+/// used by GitHub, and result in `None`.
+pub(crate) fn parse_timestamp(timestamp: &str) -> Option<SystemTime> {
+    let (date, time) = timestamp.strip_suffix('Z')?.split_once('T')?;
+
+    let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+
+    let mut time = time.splitn(3, ':').map(str::parse::<u64>);
+    let (hour, min, sec) = (time.next()?.ok()?, time.next()?.ok()?, time.next()?.ok()?);
+
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || min > 59
+        || sec > 60
+    {
+        return None;
+    }
+
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let secs = days * 86400 + hour * 3600 + min * 60 + sec;
+    Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+#[cfg(feature = "client")]
+/// Parses an HTTP date as used by the `Date` header,
+/// e.g. `"Wed, 21 Oct 2015 07:28:00 GMT"`.
+pub(crate) fn parse_http_date(date: &str) -> Option<SystemTime> {
+    let (_weekday, date) = date.strip_suffix(" GMT")?.split_once(", ")?;
+
+    let mut parts = date.splitn(4, ' ');
+    let (day, month, year, time) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    let month = match month {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+
+    parse_timestamp(&format!("{year}-{month:02}-{day}T{time}Z"))
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0) && ((year % 100 != 0) || (year % 400 == 0)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since 1970-01-01.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Returns the time elapsed since `time`, or zero if `time` is in the future.
+pub(crate) fn elapsed_since(time: SystemTime) -> Duration {
+    SystemTime::now()
+        .duration_since(time)
+        .unwrap_or(Duration::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Option<SystemTime> {
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), secs(0));
+        assert_eq!(parse_timestamp("2011-01-26T19:06:43Z"), secs(1296068803));
+    }
+
+    #[test]
+    fn timestamp_leap_day() {
+        assert_eq!(parse_timestamp("2024-02-29T00:00:00Z"), secs(1709164800));
+        assert_eq!(parse_timestamp("2023-02-29T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("2100-02-29T00:00:00Z"), None);
+        assert!(parse_timestamp("2000-02-29T00:00:00Z").is_some());
+    }
+
+    #[test]
+    fn timestamp_fraction_and_offset() {
+        assert_eq!(parse_timestamp("2011-01-26T19:06:43.123Z"), None);
+        assert_eq!(parse_timestamp("2011-01-26T19:06:43+01:00"), None);
+        assert_eq!(parse_timestamp("2011-01-26T19:06:43"), None);
+    }
+
+    #[test]
+    fn timestamp_malformed() {
+        for timestamp in [
+            "",
+            "Z",
+            "2011-01-26Z",
+            "2011-01-26 19:06:43Z",
+            "2011-13-26T19:06:43Z",
+            "2011-04-31T19:06:43Z",
+            "2011-01-00T19:06:43Z",
+            "2011-01-26T24:00:00Z",
+            "2011-01-26T19:60:00Z",
+            "2011-01-26T19:06Z",
+            "2011-01-26T19:06:4xZ",
+        ] {
+            assert_eq!(parse_timestamp(timestamp), None, "{timestamp:?}");
+        }
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn http_date() {
+        assert_eq!(parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"), secs(0));
+        assert_eq!(
+            parse_http_date("Thu, 29 Feb 2024 12:00:00 GMT"),
+            secs(1709208000)
+        );
+        assert_eq!(
+            parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"),
+            secs(1445412480)
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn http_date_malformed() {
+        for date in [
+            "",
+            "Wed, 21 Oct 2015 07:28:00",
+            "Wed, 21 Oct 2015 07:28:00 UTC",
+            "Wed, 21 Foo 2015 07:28:00 GMT",
+            "Wed 21 Oct 2015 07:28:00 GMT",
+            "Wed, 32 Oct 2015 07:28:00 GMT",
+            "Wed, 21 Oct 2015 07:28 GMT",
+            "Wednesday, 21-Oct-15 07:28:00 GMT",
+        ] {
+            assert_eq!(parse_http_date(date), None, "{date:?}");
+        }
+    }
+}
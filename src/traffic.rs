@@ -0,0 +1,63 @@
+use serde::Deserialize;
+
+/// Views of a repository during the last 14 days.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_traffic_views()`](crate::Client::get_traffic_views)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhTrafficViews {
+    pub count: u64,
+    pub uniques: u64,
+    /// Daily breakdown, oldest first.
+    pub views: Vec<GhDailyTraffic>,
+}
+
+/// Clones of a repository during the last 14 days.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_traffic_clones()`](crate::Client::get_traffic_clones)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhTrafficClones {
+    pub count: u64,
+    pub uniques: u64,
+    /// Daily breakdown, oldest first.
+    pub clones: Vec<GhDailyTraffic>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhDailyTraffic {
+    /// ISO 8601 timestamp of the start of the day, e.g. `"2016-10-10T00:00:00Z"`.
+    pub timestamp: String,
+    pub count: u64,
+    pub uniques: u64,
+}
+
+#[cfg_attr(
+    feature = "client",
+    doc = "See [`Client::get_top_referrers()`](crate::Client::get_top_referrers)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhTrafficReferrer {
+    /// Domain of the referring site, e.g. `"Google"` or `"github.com"`.
+    pub referrer: String,
+    pub count: u64,
+    pub uniques: u64,
+}
+
+#[cfg_attr(
+    feature = "client",
+    doc = "See [`Client::get_top_paths()`](crate::Client::get_top_paths)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhTrafficPath {
+    /// Path relative to the site, e.g. `"/github/hubot"`.
+    pub path: String,
+    /// Title of the page.
+    pub title: String,
+    pub count: u64,
+    pub uniques: u64,
+}
@@ -0,0 +1,156 @@
+use serde::Deserialize;
+
+/// A GitHub Actions workflow.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_workflows()`](crate::Client::get_workflows)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhWorkflow {
+    pub id: u64,
+    pub name: String,
+    /// Path of the workflow file, e.g. `".github/workflows/ci.yml"`.
+    pub path: String,
+    pub state: GhWorkflowState,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(from = "String")]
+pub enum GhWorkflowState {
+    Active,
+    Deleted,
+    DisabledFork,
+    DisabledInactivity,
+    DisabledManually,
+    /// Any other state.
+    Other(String),
+}
+
+impl GhWorkflowState {
+    /// Returns `true` if the state is [`GhWorkflowState::Active`].
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Active)
+    }
+}
+
+impl From<String> for GhWorkflowState {
+    fn from(state: String) -> Self {
+        match state.as_str() {
+            "active" => Self::Active,
+            "deleted" => Self::Deleted,
+            "disabled_fork" => Self::DisabledFork,
+            "disabled_inactivity" => Self::DisabledInactivity,
+            "disabled_manually" => Self::DisabledManually,
+            _ => Self::Other(state),
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+#[derive(Deserialize, Debug)]
+pub(crate) struct GhWorkflows {
+    pub(crate) total_count: usize,
+    pub(crate) workflows: Vec<GhWorkflow>,
+}
+
+/// A run of a GitHub Actions workflow.
+#[cfg_attr(
+    feature = "client",
+    doc = "",
+    doc = "See [`Client::get_latest_run()`](crate::Client::get_latest_run)."
+)]
+#[derive(Deserialize, Clone, Debug)]
+pub struct GhWorkflowRun {
+    pub id: u64,
+    pub name: Option<String>,
+    pub workflow_id: u64,
+    pub head_branch: Option<String>,
+    pub head_sha: String,
+    pub status: GhWorkflowRunStatus,
+    /// `None` until the run is [completed](GhWorkflowRunStatus::Completed).
+    pub conclusion: Option<GhWorkflowRunConclusion>,
+
+    #[serde(rename = "html_url")]
+    pub url: String,
+
+    /// ISO 8601 timestamp, e.g. `"2012-10-09T23:39:01Z"`.
+    pub created_at: String,
+    /// ISO 8601 timestamp, e.g. `"2012-10-09T23:39:01Z"`.
+    pub updated_at: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(from = "String")]
+pub enum GhWorkflowRunStatus {
+    Requested,
+    Queued,
+    Pending,
+    Waiting,
+    InProgress,
+    Completed,
+    /// Any other status.
+    Other(String),
+}
+
+impl From<String> for GhWorkflowRunStatus {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "requested" => Self::Requested,
+            "queued" => Self::Queued,
+            "pending" => Self::Pending,
+            "waiting" => Self::Waiting,
+            "in_progress" => Self::InProgress,
+            "completed" => Self::Completed,
+            _ => Self::Other(status),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(from = "String")]
+pub enum GhWorkflowRunConclusion {
+    Success,
+    Failure,
+    Neutral,
+    Cancelled,
+    Skipped,
+    TimedOut,
+    ActionRequired,
+    Stale,
+    StartupFailure,
+    /// Any other conclusion.
+    Other(String),
+}
+
+impl GhWorkflowRunConclusion {
+    /// Returns `true` if the conclusion is [`GhWorkflowRunConclusion::Success`].
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+}
+
+impl From<String> for GhWorkflowRunConclusion {
+    fn from(conclusion: String) -> Self {
+        match conclusion.as_str() {
+            "success" => Self::Success,
+            "failure" => Self::Failure,
+            "neutral" => Self::Neutral,
+            "cancelled" => Self::Cancelled,
+            "skipped" => Self::Skipped,
+            "timed_out" => Self::TimedOut,
+            "action_required" => Self::ActionRequired,
+            "stale" => Self::Stale,
+            "startup_failure" => Self::StartupFailure,
+            _ => Self::Other(conclusion),
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+#[derive(Deserialize, Debug)]
+pub(crate) struct GhWorkflowRuns {
+    pub(crate) workflow_runs: Vec<GhWorkflowRun>,
+}